@@ -0,0 +1,101 @@
+use crossbeam_channel::{Receiver, SendError, Sender, TryIter, unbounded};
+use std::sync::Arc;
+
+// ---------- embedded_channel ---------- //
+
+// `M` flows from the host to the view, `O` from the view back to the host.
+pub fn embedded_channel<M, O>() -> (EmbeddedHostChannel<M, O>, EmbeddedViewChannel<M, O>) {
+    new_channel(None)
+}
+
+// Like `embedded_channel`, but `notify` is called on the sending thread after every message
+// the view sends, so the host does not have to poll.
+pub fn embedded_channel_with_notify<M, O>(
+    notify: impl Fn() + Send + Sync + 'static,
+) -> (EmbeddedHostChannel<M, O>, EmbeddedViewChannel<M, O>) {
+    new_channel(Some(Arc::new(notify)))
+}
+
+fn new_channel<M, O>(
+    notify: Option<Arc<dyn Fn() + Send + Sync>>,
+) -> (EmbeddedHostChannel<M, O>, EmbeddedViewChannel<M, O>) {
+    let (host_sender, view_receiver) = unbounded();
+    let (view_sender, host_receiver) = unbounded();
+    (
+        EmbeddedHostChannel {
+            sender: host_sender,
+            receiver: host_receiver,
+        },
+        EmbeddedViewChannel {
+            receiver: view_receiver,
+            sender: EmbeddedSender {
+                sender: view_sender,
+                notify,
+            },
+        },
+    )
+}
+
+// ---------- EmbeddedHostChannel ---------- //
+
+pub struct EmbeddedHostChannel<M, O> {
+    sender: Sender<M>,
+    receiver: Receiver<O>,
+}
+
+impl<M, O> EmbeddedHostChannel<M, O> {
+    pub fn send(&self, message: M) -> Result<(), SendError<M>> {
+        self.sender.send(message)
+    }
+
+    pub fn try_recv(&self) -> Option<O> {
+        self.receiver.try_recv().ok()
+    }
+
+    pub fn try_iter(&self) -> TryIter<'_, O> {
+        self.receiver.try_iter()
+    }
+}
+
+// ---------- EmbeddedViewChannel ---------- //
+
+// Cloned into every editor that is opened, so it outlives a single `EmbeddedWindow`.
+pub struct EmbeddedViewChannel<M, O> {
+    pub(crate) receiver: Receiver<M>,
+    pub(crate) sender: EmbeddedSender<O>,
+}
+
+impl<M, O> Clone for EmbeddedViewChannel<M, O> {
+    fn clone(&self) -> Self {
+        Self {
+            receiver: self.receiver.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+// ---------- EmbeddedSender ---------- //
+
+pub struct EmbeddedSender<O> {
+    sender: Sender<O>,
+    notify: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl<O> EmbeddedSender<O> {
+    pub fn send(&self, message: O) -> Result<(), SendError<O>> {
+        self.sender.send(message)?;
+        if let Some(notify) = &self.notify {
+            notify();
+        }
+        Ok(())
+    }
+}
+
+impl<O> Clone for EmbeddedSender<O> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            notify: self.notify.clone(),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use i_slint_core::platform::Clipboard;
+use raw_window_handle::RawDisplayHandle;
+
+#[cfg(feature = "clipboard")]
+use crate::logging;
+
+#[cfg(feature = "clipboard")]
+use copypasta::ClipboardProvider;
+
+// ---------- EmbeddedClipboard ---------- //
+
+// Starts out in memory, which is what headless windows keep using. The first window opened on a
+// thread replaces it with the system clipboard.
+pub(crate) struct EmbeddedClipboard {
+    clipboard: EmbeddedClipboardBackend,
+    selection: EmbeddedClipboardBackend,
+}
+
+impl Default for EmbeddedClipboard {
+    fn default() -> Self {
+        Self {
+            clipboard: EmbeddedClipboardBackend::Memory(String::new()),
+            selection: EmbeddedClipboardBackend::Memory(String::new()),
+        }
+    }
+}
+
+impl EmbeddedClipboard {
+    #[cfg(feature = "clipboard")]
+    pub(crate) fn init(&mut self, display_handle: RawDisplayHandle) {
+        if matches!(self.clipboard, EmbeddedClipboardBackend::System(_)) {
+            return;
+        }
+
+        let Some((clipboard, selection)) = Self::system_clipboards(display_handle) else {
+            return;
+        };
+        self.clipboard = EmbeddedClipboardBackend::System(clipboard);
+        self.selection = match selection {
+            Some(selection) => EmbeddedClipboardBackend::System(selection),
+            None => EmbeddedClipboardBackend::Unsupported,
+        };
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    pub(crate) fn init(&mut self, _display_handle: RawDisplayHandle) {}
+
+    pub(crate) fn set_text(&mut self, text: &str, clipboard: Clipboard) {
+        if let Some(backend) = self.backend(clipboard) {
+            backend.set_text(text);
+        }
+    }
+
+    pub(crate) fn text(&mut self, clipboard: Clipboard) -> Option<String> {
+        self.backend(clipboard)?.text()
+    }
+
+    fn backend(&mut self, clipboard: Clipboard) -> Option<&mut EmbeddedClipboardBackend> {
+        match clipboard {
+            Clipboard::DefaultClipboard => Some(&mut self.clipboard),
+            Clipboard::SelectionClipboard => Some(&mut self.selection),
+            _ => None,
+        }
+    }
+
+    // Returns the clipboard and, if supported, the primary selection.
+    #[cfg(feature = "clipboard")]
+    fn system_clipboards(
+        display_handle: RawDisplayHandle,
+    ) -> Option<(
+        Box<dyn ClipboardProvider>,
+        Option<Box<dyn ClipboardProvider>>,
+    )> {
+        cfg_if::cfg_if! {
+            if #[cfg(all(unix, not(target_os = "macos")))] {
+                use copypasta::{wayland_clipboard, x11_clipboard};
+
+                match display_handle {
+                    RawDisplayHandle::Wayland(handle) => {
+                        // The display is owned by the host and outlives the editor.
+                        let (selection, clipboard) = unsafe {
+                            wayland_clipboard::create_clipboards_from_external(
+                                handle.display.as_ptr(),
+                            )
+                        };
+                        Some((
+                            Box::new(clipboard) as Box<dyn ClipboardProvider>,
+                            Some(Box::new(selection) as Box<dyn ClipboardProvider>),
+                        ))
+                    }
+                    // The X11 clipboards open their own connections.
+                    RawDisplayHandle::Xlib(_) | RawDisplayHandle::Xcb(_) => {
+                        let clipboard = x11_clipboard::X11ClipboardContext::<
+                            x11_clipboard::Clipboard,
+                        >::new()
+                        .ok()?;
+                        let selection =
+                            x11_clipboard::X11ClipboardContext::<x11_clipboard::Primary>::new()
+                                .ok();
+                        Some((
+                            Box::new(clipboard) as Box<dyn ClipboardProvider>,
+                            selection.map(|s| Box::new(s) as Box<dyn ClipboardProvider>),
+                        ))
+                    }
+                    _ => None,
+                }
+            } else {
+                let _ = display_handle;
+                let clipboard = copypasta::ClipboardContext::new().ok()?;
+                Some((Box::new(clipboard) as Box<dyn ClipboardProvider>, None))
+            }
+        }
+    }
+}
+
+// ---------- EmbeddedClipboardBackend ---------- //
+
+enum EmbeddedClipboardBackend {
+    #[cfg(feature = "clipboard")]
+    Unsupported,
+    Memory(String),
+    #[cfg(feature = "clipboard")]
+    System(Box<dyn ClipboardProvider>),
+}
+
+impl EmbeddedClipboardBackend {
+    fn set_text(&mut self, text: &str) {
+        match self {
+            #[cfg(feature = "clipboard")]
+            Self::Unsupported => {}
+            Self::Memory(contents) => *contents = text.into(),
+            #[cfg(feature = "clipboard")]
+            Self::System(provider) => {
+                if let Err(err) = provider.set_contents(text.into()) {
+                    logging::warn(logging::CLIPBOARD, err);
+                }
+            }
+        }
+    }
+
+    fn text(&mut self) -> Option<String> {
+        match self {
+            #[cfg(feature = "clipboard")]
+            Self::Unsupported => None,
+            Self::Memory(contents) => Some(contents.clone()),
+            #[cfg(feature = "clipboard")]
+            Self::System(provider) => provider.get_contents().ok(),
+        }
+    }
+}
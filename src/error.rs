@@ -0,0 +1,118 @@
+use i_slint_core::platform::SetPlatformError;
+use std::fmt::{Display, Formatter};
+
+// ---------- EmbeddedError ---------- //
+
+#[derive(Debug)]
+pub enum EmbeddedError {
+    // Another platform was set on the window thread.
+    Platform(SetPlatformError),
+    Renderer(EmbeddedRendererError),
+    // The window was closed before its build closure could report back.
+    WindowNotOpened,
+    WindowClosed,
+    // `EmbeddedWindow::proxy` was asked for another type than the one of the view.
+    ViewTypeMismatch,
+}
+
+impl Display for EmbeddedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Platform(err) => write!(f, "Platform error: {err}"),
+            Self::Renderer(err) => err.fmt(f),
+            Self::WindowNotOpened => f.write_str("Window was not opened"),
+            Self::WindowClosed => f.write_str("Window is closed"),
+            Self::ViewTypeMismatch => f.write_str("View type does not match the window's view"),
+        }
+    }
+}
+
+impl std::error::Error for EmbeddedError {}
+
+impl From<SetPlatformError> for EmbeddedError {
+    fn from(err: SetPlatformError) -> Self {
+        Self::Platform(err)
+    }
+}
+
+impl From<EmbeddedRendererError> for EmbeddedError {
+    fn from(err: EmbeddedRendererError) -> Self {
+        Self::Renderer(err)
+    }
+}
+
+// ---------- EmbeddedRendererError ---------- //
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmbeddedRendererErrorKind {
+    Display,
+    Config,
+    Context,
+    Surface,
+    SetWindow,
+    Resize,
+    Buffer,
+    Render,
+    Present,
+}
+
+// The cause is kept as a message, since the error is sent back from the window thread and most
+// backend errors are not `Send`.
+#[derive(Clone, Debug)]
+pub struct EmbeddedRendererError {
+    renderer: &'static str,
+    kind: EmbeddedRendererErrorKind,
+    message: String,
+}
+
+impl EmbeddedRendererError {
+    pub(crate) fn new(
+        renderer: &'static str,
+        kind: EmbeddedRendererErrorKind,
+        message: impl Display,
+    ) -> Self {
+        Self {
+            renderer,
+            kind,
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn map<E: Display>(
+        renderer: &'static str,
+        kind: EmbeddedRendererErrorKind,
+    ) -> impl FnOnce(E) -> Self {
+        move |err| Self::new(renderer, kind, err)
+    }
+
+    pub fn renderer(&self) -> &'static str {
+        self.renderer
+    }
+
+    pub fn kind(&self) -> EmbeddedRendererErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for EmbeddedRendererError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            EmbeddedRendererErrorKind::Display => "display",
+            EmbeddedRendererErrorKind::Config => "config",
+            EmbeddedRendererErrorKind::Context => "context",
+            EmbeddedRendererErrorKind::Surface => "surface",
+            EmbeddedRendererErrorKind::SetWindow => "set window",
+            EmbeddedRendererErrorKind::Resize => "resize",
+            EmbeddedRendererErrorKind::Buffer => "buffer",
+            EmbeddedRendererErrorKind::Render => "render",
+            EmbeddedRendererErrorKind::Present => "present",
+        };
+        write!(f, "{} {kind} error: {}", self.renderer, self.message)
+    }
+}
+
+impl std::error::Error for EmbeddedRendererError {}
@@ -0,0 +1,163 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Wake, Waker},
+};
+
+// ---------- EmbeddedExecutor ---------- //
+
+// Polls the futures of a window on its thread. Wakers may be called from any thread and only flag
+// their task and the executor, the polling happens with the next frame tick.
+#[derive(Default)]
+pub(crate) struct EmbeddedExecutor {
+    tasks: RefCell<Vec<EmbeddedTask>>,
+    woken: Arc<AtomicBool>,
+}
+
+impl EmbeddedExecutor {
+    pub(crate) fn spawn(&self, future: impl Future<Output = ()> + 'static) {
+        self.tasks.borrow_mut().push(EmbeddedTask {
+            future: Box::pin(future),
+            waker: Arc::new(EmbeddedTaskWaker {
+                woken: AtomicBool::new(true),
+                executor_woken: self.woken.clone(),
+            }),
+        });
+        self.woken.store(true, Ordering::Release);
+    }
+
+    // Returns whether any task was polled. Cheap if no task was woken since the last call.
+    pub(crate) fn poll(&self) -> bool {
+        if !self.woken.swap(false, Ordering::AcqRel) {
+            return false;
+        }
+
+        // Taken out, so tasks can spawn new ones while being polled.
+        let mut tasks = std::mem::take(&mut *self.tasks.borrow_mut());
+
+        let mut polled = false;
+        tasks.retain_mut(|task| {
+            if !task.waker.woken.swap(false, Ordering::AcqRel) {
+                return true;
+            }
+            polled = true;
+
+            let waker = Waker::from(task.waker.clone());
+            task.future
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_pending()
+        });
+
+        let mut spawned = self.tasks.borrow_mut();
+        tasks.append(&mut spawned);
+        *spawned = tasks;
+        polled
+    }
+}
+
+struct EmbeddedTask {
+    future: Pin<Box<dyn Future<Output = ()>>>,
+    waker: Arc<EmbeddedTaskWaker>,
+}
+
+struct EmbeddedTaskWaker {
+    woken: AtomicBool,
+    executor_woken: Arc<AtomicBool>,
+}
+
+impl Wake for EmbeddedTaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        self.executor_woken.store(true, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, future::poll_fn, rc::Rc, task::Poll};
+
+    use super::*;
+
+    #[test]
+    fn spawn_from_task() {
+        let executor = Rc::new(EmbeddedExecutor::default());
+        let done = Rc::new(Cell::new(false));
+
+        executor.spawn({
+            let executor = Rc::downgrade(&executor);
+            let done = done.clone();
+            async move {
+                executor
+                    .upgrade()
+                    .unwrap()
+                    .spawn(async move { done.set(true) });
+            }
+        });
+
+        // The new task is polled with the next call.
+        assert!(executor.poll());
+        assert!(!done.get());
+        assert!(executor.poll());
+        assert!(done.get());
+        assert!(executor.tasks.borrow().is_empty());
+    }
+
+    #[test]
+    fn task_wakes_itself() {
+        let executor = EmbeddedExecutor::default();
+        let polls = Rc::new(Cell::new(0));
+
+        executor.spawn({
+            let polls = polls.clone();
+            poll_fn(move |cx| {
+                polls.set(polls.get() + 1);
+                match polls.get() {
+                    1 => {
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                    _ => Poll::Ready(()),
+                }
+            })
+        });
+
+        assert!(executor.poll());
+        assert_eq!(polls.get(), 1);
+        assert!(executor.poll());
+        assert_eq!(polls.get(), 2);
+        assert!(!executor.poll());
+        assert!(executor.tasks.borrow().is_empty());
+    }
+
+    #[test]
+    fn poll_without_wakes() {
+        let executor = EmbeddedExecutor::default();
+        assert!(!executor.poll());
+
+        let polls = Rc::new(Cell::new(0));
+        executor.spawn({
+            let polls = polls.clone();
+            poll_fn(move |_| {
+                polls.set(polls.get() + 1);
+                Poll::<()>::Pending
+            })
+        });
+
+        // A pending task which was not woken is left alone.
+        assert!(executor.poll());
+        assert!(!executor.poll());
+        assert!(!executor.poll());
+        assert_eq!(polls.get(), 1);
+        assert_eq!(executor.tasks.borrow().len(), 1);
+    }
+}
@@ -0,0 +1,132 @@
+use crossbeam_channel::Receiver;
+use i_slint_core::{
+    api::{LogicalSize, Window},
+    graphics::{Rgba8Pixel, SharedPixelBuffer},
+    platform::update_timers_and_animations,
+    window::WindowAdapter,
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    EmbeddedError, EmbeddedFrameCounters, EmbeddedRendererType, EmbeddedSizeConstraints,
+    EmbeddedView, EmbeddedViewChannel, EmbeddedWindowInterface,
+    platform::{EmbeddedClock, EmbeddedPlatform, EmbeddedWindowId},
+    renderer::{EmbeddedHeadlessBuffer, EmbeddedHeadlessRendererAdapter, EmbeddedRendererAdapter},
+    window_adapter::EmbeddedWindowAdapter,
+};
+
+// ---------- EmbeddedHeadlessWindow ---------- //
+
+// Drives a view without a baseview window, e.g. to snapshot test it in CI or to render preset
+// thumbnails. Everything runs on the calling thread.
+pub struct EmbeddedHeadlessWindow<M, V> {
+    receiver: Receiver<M>,
+    view: V,
+    window_adapter: Rc<EmbeddedWindowAdapter>,
+    window_id: EmbeddedWindowId,
+    buffer: Rc<RefCell<EmbeddedHeadlessBuffer>>,
+}
+
+impl<M: Send, V: EmbeddedView<M>> EmbeddedHeadlessWindow<M, V> {
+    pub fn new<B, O>(
+        size: LogicalSize,
+        user_scale_factor: f32,
+        channel: EmbeddedViewChannel<M, O>,
+        build: B,
+    ) -> Result<Self, EmbeddedError>
+    where
+        B: FnOnce(EmbeddedWindowInterface<O>) -> V,
+    {
+        EmbeddedPlatform::install()?;
+
+        let renderer_adapter = EmbeddedHeadlessRendererAdapter::default();
+        let buffer = renderer_adapter.buffer();
+        let window_adapter = EmbeddedWindowAdapter::new(
+            size,
+            user_scale_factor,
+            baseview::WindowScalePolicy::ScaleFactor(1.0),
+            vec![(
+                EmbeddedRendererType::Software,
+                Box::new(renderer_adapter) as Box<dyn EmbeddedRendererAdapter>,
+            )],
+        );
+        let window_id = EmbeddedPlatform::register_window(window_adapter.clone());
+
+        let interface = EmbeddedWindowInterface::new(window_adapter.clone(), channel.sender);
+        let view = EmbeddedPlatform::with_active_window(window_id, || build(interface));
+
+        Ok(Self {
+            receiver: channel.receiver,
+            view,
+            window_adapter,
+            window_id,
+            buffer,
+        })
+    }
+
+    pub fn view(&self) -> &V {
+        &self.view
+    }
+
+    pub fn window(&self) -> &Window {
+        self.window_adapter.window()
+    }
+
+    // Events are converted like the ones of a real window, so input can be scripted with the
+    // same types baseview reports.
+    pub fn dispatch_event(&self, event: baseview::Event) -> baseview::EventStatus {
+        EmbeddedPlatform::with_active_window(self.window_id, || self.window_adapter.on_event(event))
+    }
+
+    // Handles pending messages, advances timers and animations and renders.
+    pub fn advance_frame(&self) -> Result<(), EmbeddedError> {
+        EmbeddedPlatform::with_active_window(self.window_id, || {
+            self.window_adapter.count_frame();
+            update_timers_and_animations();
+
+            for message in self.receiver.try_iter() {
+                self.view.on_message(message);
+            }
+
+            EmbeddedPlatform::process_events();
+            self.window_adapter.poll_executor();
+            self.window_adapter.render_frame()
+        })?;
+        Ok(())
+    }
+
+    // Applies to every window on this thread.
+    pub fn set_clock(&self, clock: EmbeddedClock) {
+        EmbeddedPlatform::set_clock(clock);
+    }
+
+    // Moves the manual clock forward and advances a frame, so animations can be checked frame by
+    // frame.
+    pub fn advance_time(&self, duration: Duration) -> Result<(), EmbeddedError> {
+        EmbeddedPlatform::advance_time(duration);
+        self.advance_frame()
+    }
+
+    pub fn size_constraints(&self) -> EmbeddedSizeConstraints {
+        *self
+            .window_adapter
+            .shared_size_constraints()
+            .lock()
+            .unwrap()
+    }
+
+    pub fn frame_counters(&self) -> EmbeddedFrameCounters {
+        self.window_adapter.frame_counters()
+    }
+
+    // The last rendered frame as non-premultiplied RGBA.
+    pub fn grab_pixels(&self) -> SharedPixelBuffer<Rgba8Pixel> {
+        self.buffer.borrow().to_rgba()
+    }
+}
+
+impl<M, V> Drop for EmbeddedHeadlessWindow<M, V> {
+    fn drop(&mut self) {
+        EmbeddedPlatform::unregister_window(self.window_id);
+    }
+}
@@ -0,0 +1,37 @@
+use i_slint_core::api::{LogicalPosition, LogicalSize};
+use std::ops::Range;
+
+// ---------- EmbeddedInputMethod ---------- //
+
+// Baseview has no IME support, so composition is fed in by whoever owns one, e.g. the host or a
+// platform specific text input client.
+pub trait EmbeddedInputMethod {
+    fn on_request(&self, request: EmbeddedImeRequest);
+}
+
+// The cursor rectangle is in logical coordinates of the baseview window, i.e. it includes the user
+// scale factor.
+#[derive(Clone, Debug)]
+pub enum EmbeddedImeRequest {
+    Enable {
+        cursor_position: LogicalPosition,
+        cursor_size: LogicalSize,
+    },
+    Update {
+        cursor_position: LogicalPosition,
+        cursor_size: LogicalSize,
+    },
+    Disable,
+}
+
+// ---------- EmbeddedImeEvent ---------- //
+
+// The selection is a byte range within the pre-edit text.
+#[derive(Clone, Debug)]
+pub enum EmbeddedImeEvent {
+    Preedit {
+        text: String,
+        selection: Option<Range<usize>>,
+    },
+    Commit(String),
+}
@@ -0,0 +1,218 @@
+use i_slint_core::SharedString;
+use keyboard_types::{Code, Key};
+
+// ---------- Special keys ---------- //
+
+// Slint's private characters for named keys, see `i_slint_common::for_each_special_keys`.
+// Control and meta are swapped like in `EmbeddedWindowAdapter::convert_modifier`.
+const SPECIAL_KEYS: &[(Key, char)] = &[
+    (Key::Backspace, '\u{0008}'),
+    (Key::Tab, '\u{0009}'),
+    (Key::Enter, '\u{000a}'),
+    (Key::Escape, '\u{001b}'),
+    (Key::Delete, '\u{007f}'),
+    (Key::Shift, '\u{0010}'),
+    (Key::Control, '\u{0017}'),
+    (Key::Alt, '\u{0012}'),
+    (Key::AltGraph, '\u{0013}'),
+    (Key::CapsLock, '\u{0014}'),
+    (Key::Meta, '\u{0011}'),
+    (Key::Super, '\u{0017}'),
+    (Key::ArrowUp, '\u{F700}'),
+    (Key::ArrowDown, '\u{F701}'),
+    (Key::ArrowLeft, '\u{F702}'),
+    (Key::ArrowRight, '\u{F703}'),
+    (Key::F1, '\u{F704}'),
+    (Key::F2, '\u{F705}'),
+    (Key::F3, '\u{F706}'),
+    (Key::F4, '\u{F707}'),
+    (Key::F5, '\u{F708}'),
+    (Key::F6, '\u{F709}'),
+    (Key::F7, '\u{F70A}'),
+    (Key::F8, '\u{F70B}'),
+    (Key::F9, '\u{F70C}'),
+    (Key::F10, '\u{F70D}'),
+    (Key::F11, '\u{F70E}'),
+    (Key::F12, '\u{F70F}'),
+    (Key::F13, '\u{F710}'),
+    (Key::F14, '\u{F711}'),
+    (Key::F15, '\u{F712}'),
+    (Key::F16, '\u{F713}'),
+    (Key::F17, '\u{F714}'),
+    (Key::F18, '\u{F715}'),
+    (Key::F19, '\u{F716}'),
+    (Key::F20, '\u{F717}'),
+    (Key::F21, '\u{F718}'),
+    (Key::F22, '\u{F719}'),
+    (Key::F23, '\u{F71A}'),
+    (Key::F24, '\u{F71B}'),
+    (Key::Insert, '\u{F727}'),
+    (Key::Home, '\u{F729}'),
+    (Key::End, '\u{F72B}'),
+    (Key::PageUp, '\u{F72C}'),
+    (Key::PageDown, '\u{F72D}'),
+    (Key::ScrollLock, '\u{F72F}'),
+    (Key::Pause, '\u{F730}'),
+    (Key::PrintScreen, '\u{F731}'),
+    (Key::BrowserStop, '\u{F734}'),
+    (Key::ContextMenu, '\u{F735}'),
+];
+
+// Keys which Slint distinguishes by location. Super shares the group of control, like in
+// `SPECIAL_KEYS`.
+const LOCATED_KEYS: &[((Key, Code), char)] = &[
+    ((Key::Shift, Code::ShiftRight), '\u{0015}'),
+    ((Key::Control, Code::ControlRight), '\u{0018}'),
+    ((Key::Meta, Code::MetaRight), '\u{0016}'),
+    ((Key::Super, Code::MetaRight), '\u{0018}'),
+];
+
+// Fallback for numpad keys which the platform did not translate to a key.
+const NUMPAD_KEYS: &[(Code, char)] = &[
+    (Code::Numpad0, '0'),
+    (Code::Numpad1, '1'),
+    (Code::Numpad2, '2'),
+    (Code::Numpad3, '3'),
+    (Code::Numpad4, '4'),
+    (Code::Numpad5, '5'),
+    (Code::Numpad6, '6'),
+    (Code::Numpad7, '7'),
+    (Code::Numpad8, '8'),
+    (Code::Numpad9, '9'),
+    (Code::NumpadAdd, '+'),
+    (Code::NumpadSubtract, '-'),
+    (Code::NumpadMultiply, '*'),
+    (Code::NumpadStar, '*'),
+    (Code::NumpadDivide, '/'),
+    (Code::NumpadDecimal, '.'),
+    (Code::NumpadComma, ','),
+    (Code::NumpadEqual, '='),
+    (Code::NumpadHash, '#'),
+    (Code::NumpadParenLeft, '('),
+    (Code::NumpadParenRight, ')'),
+    (Code::NumpadBackspace, '\u{0008}'),
+    (Code::NumpadEnter, '\u{000a}'),
+];
+
+pub(crate) fn convert_key(key: &Key, code: Code) -> Option<SharedString> {
+    if let Key::Character(text) = key {
+        return Some(text.as_str().into());
+    }
+
+    let numpad = || match key {
+        Key::Unidentified => find(NUMPAD_KEYS, &code),
+        _ => None,
+    };
+
+    find(LOCATED_KEYS, &(key.clone(), code))
+        .or_else(|| find(SPECIAL_KEYS, key))
+        .or_else(numpad)
+        .map(|c| c.to_string().into())
+}
+
+fn find<K: PartialEq>(table: &[(K, char)], key: &K) -> Option<char> {
+    table.iter().find(|(k, _)| k == key).map(|(_, c)| *c)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn slint_special_keys() -> Vec<(char, &'static str)> {
+        macro_rules! special_keys {
+            ($($char:literal # $name:ident # $($_qt:ident)|* # $($_winit:ident $(($_pos:ident))?)|* # $($_xkb:ident)|* ;)*) => {
+                vec![$(($char, stringify!($name))),*]
+            };
+        }
+        i_slint_common::for_each_special_keys!(special_keys)
+    }
+
+    // The keys baseview reports for a Slint key, `None` if there is none. Control and meta are
+    // swapped like in `SPECIAL_KEYS`.
+    fn baseview_key(name: &str) -> Option<(Key, Code)> {
+        Some(match name {
+            "Backspace" => (Key::Backspace, Code::Backspace),
+            "Tab" => (Key::Tab, Code::Tab),
+            "Return" => (Key::Enter, Code::Enter),
+            "Escape" => (Key::Escape, Code::Escape),
+            "Backtab" => return None,
+            "Delete" => (Key::Delete, Code::Delete),
+            "Shift" => (Key::Shift, Code::ShiftLeft),
+            "ShiftR" => (Key::Shift, Code::ShiftRight),
+            "Control" => (Key::Meta, Code::MetaLeft),
+            "ControlR" => (Key::Meta, Code::MetaRight),
+            "Meta" => (Key::Control, Code::ControlLeft),
+            "MetaR" => (Key::Control, Code::ControlRight),
+            "Alt" => (Key::Alt, Code::AltLeft),
+            "AltGr" => (Key::AltGraph, Code::AltRight),
+            "CapsLock" => (Key::CapsLock, Code::CapsLock),
+            "Space" => (Key::Character(" ".into()), Code::Space),
+            "UpArrow" => (Key::ArrowUp, Code::ArrowUp),
+            "DownArrow" => (Key::ArrowDown, Code::ArrowDown),
+            "LeftArrow" => (Key::ArrowLeft, Code::ArrowLeft),
+            "RightArrow" => (Key::ArrowRight, Code::ArrowRight),
+            "Insert" => (Key::Insert, Code::Insert),
+            "Home" => (Key::Home, Code::Home),
+            "End" => (Key::End, Code::End),
+            "PageUp" => (Key::PageUp, Code::PageUp),
+            "PageDown" => (Key::PageDown, Code::PageDown),
+            "ScrollLock" => (Key::ScrollLock, Code::ScrollLock),
+            "Pause" => (Key::Pause, Code::Pause),
+            "SysReq" => (Key::PrintScreen, Code::PrintScreen),
+            "Stop" => (Key::BrowserStop, Code::BrowserStop),
+            "Menu" => (Key::ContextMenu, Code::ContextMenu),
+            name if name.starts_with('F') => (
+                Key::from_str(name).unwrap(),
+                Code::from_str(name).unwrap_or(Code::Unidentified),
+            ),
+            name => panic!("Slint key {name} has no baseview counterpart"),
+        })
+    }
+
+    #[test]
+    fn special_keys_match_slint() {
+        for (character, name) in slint_special_keys() {
+            let Some((key, code)) = baseview_key(name) else {
+                continue;
+            };
+            assert_eq!(
+                convert_key(&key, code),
+                Some(character.to_string().into()),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn unlocated_modifiers() {
+        // Platforms which do not report the location still get the left key.
+        assert_eq!(
+            convert_key(&Key::Shift, Code::Unidentified),
+            convert_key(&Key::Shift, Code::ShiftLeft)
+        );
+        assert_eq!(
+            convert_key(&Key::Super, Code::Unidentified),
+            convert_key(&Key::Control, Code::ControlLeft)
+        );
+        // Both super keys are in the group of the `SUPER` modifier.
+        assert_eq!(
+            convert_key(&Key::Super, Code::MetaRight),
+            convert_key(&Key::Control, Code::ControlRight)
+        );
+    }
+
+    #[test]
+    fn numpad_fallback() {
+        assert_eq!(
+            convert_key(&Key::Unidentified, Code::Numpad7),
+            Some("7".into())
+        );
+        assert_eq!(
+            convert_key(&Key::Character("7".into()), Code::Numpad7),
+            Some("7".into())
+        );
+        assert_eq!(convert_key(&Key::Unidentified, Code::KeyA), None);
+    }
+}
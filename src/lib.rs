@@ -1,7 +1,37 @@
+#[cfg(not(any(feature = "femtovg", feature = "skia", feature = "software")))]
+compile_error!("At least one renderer feature has to be enabled");
+
+mod channel;
+mod clipboard;
+mod error;
+mod executor;
+#[cfg(feature = "software")]
+mod headless;
+mod ime;
+mod keyboard;
+mod logging;
 mod platform;
 mod renderer;
 mod window;
 mod window_adapter;
 
 pub use baseview::WindowScalePolicy;
-pub use window::{EmbeddedRendererType, EmbeddedView, EmbeddedWindow, EmbeddedWindowInterface};
+pub use channel::{
+    EmbeddedHostChannel, EmbeddedSender, EmbeddedViewChannel, embedded_channel,
+    embedded_channel_with_notify,
+};
+pub use error::{EmbeddedError, EmbeddedRendererError, EmbeddedRendererErrorKind};
+#[cfg(feature = "software")]
+pub use headless::EmbeddedHeadlessWindow;
+pub use ime::{EmbeddedImeEvent, EmbeddedImeRequest, EmbeddedInputMethod};
+pub use logging::set_log_sink;
+#[cfg(feature = "software")]
+pub use platform::EmbeddedClock;
+pub use window::{
+    EmbeddedFrameCounters, EmbeddedFramePacing, EmbeddedRendererType, EmbeddedSizeConstraints,
+    EmbeddedView, EmbeddedWindow, EmbeddedWindowInterface, EmbeddedWindowProxy,
+};
+#[cfg(feature = "femtovg")]
+pub use window::{EmbeddedSwapInterval, FemtoVGApi, FemtoVGOptions};
+#[cfg(feature = "skia")]
+pub use window::{SkiaOptions, SkiaSurface};
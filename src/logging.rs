@@ -0,0 +1,57 @@
+use log::{Level, Record};
+use std::{fmt::Display, sync::RwLock};
+
+// ---------- Targets ---------- //
+
+#[cfg(feature = "clipboard")]
+pub(crate) const CLIPBOARD: &str = "slint_baseview::clipboard";
+pub(crate) const RENDERER: &str = "slint_baseview::renderer";
+// Slint's own `debug()` output.
+pub(crate) const SLINT: &str = "slint_baseview::slint";
+pub(crate) const WINDOW: &str = "slint_baseview::window";
+
+// ---------- Sink ---------- //
+
+type EmbeddedLogSink = Box<dyn Fn(&Record<'_>) + Send + Sync>;
+
+static LOG_SINK: RwLock<Option<EmbeddedLogSink>> = RwLock::new(None);
+
+// Replaces the `log` facade for this crate, since every plugin in the host process would share the
+// global logger. `None` goes back to the facade.
+pub fn set_log_sink(sink: Option<EmbeddedLogSink>) {
+    *LOG_SINK.write().unwrap() = sink;
+}
+
+pub(crate) fn error(target: &'static str, message: impl Display) {
+    emit(Level::Error, target, message);
+}
+
+pub(crate) fn warn(target: &'static str, message: impl Display) {
+    emit(Level::Warn, target, message);
+}
+
+pub(crate) fn debug(target: &'static str, message: impl Display) {
+    emit(Level::Debug, target, message);
+}
+
+fn emit(level: Level, target: &'static str, message: impl Display) {
+    emit_record(
+        &Record::builder()
+            .level(level)
+            .target(target)
+            .args(format_args!("{message}"))
+            .build(),
+    );
+}
+
+fn emit_record(record: &Record<'_>) {
+    match LOG_SINK.read().unwrap().as_ref() {
+        Some(sink) => sink(record),
+        None => {
+            let logger = log::logger();
+            if record.level() <= log::max_level() && logger.enabled(record.metadata()) {
+                logger.log(record);
+            }
+        }
+    }
+}
@@ -1,7 +1,23 @@
-use i_slint_core::{api::PlatformError, platform::Platform, window::WindowAdapter};
-use std::{cell::RefCell, rc::Rc};
+use crossbeam_channel::{Receiver, Sender, unbounded};
+use i_slint_core::{
+    api::{EventLoopError, PlatformError},
+    platform::{Clipboard, EventLoopProxy, Platform, SetPlatformError, set_platform},
+    window::WindowAdapter,
+};
+use raw_window_handle::RawDisplayHandle;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::ThreadId,
+    time::{Duration, Instant},
+};
 
-use crate::window_adapter::EmbeddedWindowAdapter;
+use crate::{clipboard::EmbeddedClipboard, logging, window_adapter::EmbeddedWindowAdapter};
 
 // ---------- EmbeddedPlatform ---------- //
 
@@ -10,15 +26,452 @@ pub(crate) struct EmbeddedPlatform {}
 
 impl EmbeddedPlatform {
     thread_local! {
-        pub(crate) static WINDOW_ADAPTER_INNER: RefCell<Option<Rc<EmbeddedWindowAdapter>>> = Default::default();
+        static WINDOW_REGISTRY: RefCell<EmbeddedWindowRegistry> = Default::default();
+        static EVENT_QUEUE: (Sender<EmbeddedEvent>, Receiver<EmbeddedEvent>) = unbounded();
+        static CLIPBOARD: RefCell<EmbeddedClipboard> = Default::default();
+        static CLOCK: RefCell<EmbeddedClockState> =
+            RefCell::new(EmbeddedClockState::Real(Instant::now()));
+        static INSTALLED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    // Slint's platform is per thread, and baseview runs every editor on its own thread on X11.
+    pub(crate) fn install() -> Result<(), SetPlatformError> {
+        if Self::INSTALLED.get() {
+            return Ok(());
+        }
+        set_platform(Box::new(Self::default()))?;
+        Self::INSTALLED.set(true);
+        Ok(())
+    }
+
+    // Monotonic, so timers and animations are not affected by changes of the system time.
+    pub(crate) fn now() -> Duration {
+        Self::CLOCK.with_borrow(|c| match c {
+            EmbeddedClockState::Real(start) => start.elapsed(),
+            #[cfg(feature = "software")]
+            EmbeddedClockState::Manual(now) => *now,
+        })
+    }
+
+    // Switching continues from the current time, so time never goes backwards.
+    #[cfg(feature = "software")]
+    pub(crate) fn set_clock(clock: EmbeddedClock) {
+        let now = Self::now();
+        Self::CLOCK.set(match clock {
+            EmbeddedClock::Real => {
+                let instant = Instant::now();
+                EmbeddedClockState::Real(instant.checked_sub(now).unwrap_or(instant))
+            }
+            EmbeddedClock::Manual => EmbeddedClockState::Manual(now),
+        });
+    }
+
+    // Only affects the manual clock.
+    #[cfg(feature = "software")]
+    pub(crate) fn advance_time(duration: Duration) {
+        Self::CLOCK.with_borrow_mut(|c| {
+            if let EmbeddedClockState::Manual(now) = c {
+                *now += duration;
+            }
+        });
+    }
+
+    pub(crate) fn init_clipboard(display_handle: RawDisplayHandle) {
+        Self::CLIPBOARD.with_borrow_mut(|c| c.init(display_handle));
+    }
+
+    pub(crate) fn register_window(window_adapter: Rc<EmbeddedWindowAdapter>) -> EmbeddedWindowId {
+        Self::register(window_adapter, false)
+    }
+
+    pub(crate) fn register_popup_window(
+        window_adapter: Rc<EmbeddedWindowAdapter>,
+    ) -> EmbeddedWindowId {
+        Self::register(window_adapter, true)
+    }
+
+    fn register(window_adapter: Rc<EmbeddedWindowAdapter>, claimed: bool) -> EmbeddedWindowId {
+        let (id, first) = Self::WINDOW_REGISTRY
+            .with_borrow_mut(|r| (r.register(window_adapter, claimed), r.windows.len() == 1));
+        if first {
+            let sender = Self::EVENT_QUEUE.with(|(sender, _)| sender.clone());
+            EVENT_LOOPS
+                .lock()
+                .unwrap()
+                .push((std::thread::current().id(), sender));
+        }
+        id
+    }
+
+    pub(crate) fn unregister_window(id: EmbeddedWindowId) -> Option<Rc<EmbeddedWindowAdapter>> {
+        let (window_adapter, last) = Self::WINDOW_REGISTRY.with_borrow_mut(|r| {
+            let window_adapter = r.unregister(id);
+            (window_adapter, r.windows.is_empty())
+        });
+        if window_adapter.is_some() && last {
+            let thread_id = std::thread::current().id();
+            EVENT_LOOPS.lock().unwrap().retain(|(t, _)| *t != thread_id);
+        }
+        window_adapter
+    }
+
+    pub(crate) fn window_adapter(id: EmbeddedWindowId) -> Option<Rc<EmbeddedWindowAdapter>> {
+        Self::WINDOW_REGISTRY.with_borrow(|r| r.window_adapter(id))
+    }
+
+    // Runs the closures posted through the event loop proxy. Called by every window on this
+    // thread, whichever comes first handles them.
+    pub(crate) fn process_events() {
+        // Collected first, so events posted while processing are handled with the next frame.
+        let events: Vec<_> = Self::EVENT_QUEUE.with(|(_, receiver)| receiver.try_iter().collect());
+        for event in events {
+            match event {
+                EmbeddedEvent::Invoke(f) => f(),
+                EmbeddedEvent::Quit(id) => {
+                    if let Some(window_adapter) = Self::window_adapter(id) {
+                        window_adapter.request_close();
+                    }
+                }
+            }
+        }
+    }
+
+    // Makes `id` the target of `create_window_adapter` while `f` runs. Scopes nest, so a window
+    // opened from within another window's handler does not steal its adapter.
+    pub(crate) fn with_active_window<R>(id: EmbeddedWindowId, f: impl FnOnce() -> R) -> R {
+        Self::WINDOW_REGISTRY.with_borrow_mut(|r| r.active.push(id));
+        let _guard = ActiveWindowGuard(id);
+        f()
     }
 }
 
 impl Platform for EmbeddedPlatform {
     fn create_window_adapter(&self) -> Result<Rc<dyn WindowAdapter>, PlatformError> {
-        Self::WINDOW_ADAPTER_INNER.with_borrow_mut(|a| match a.take() {
-            Some(a) => Ok(a as _),
-            None => Err(PlatformError::Other("No `WINDOW_ADAPTER_INNER`".into())),
-        })
+        Self::WINDOW_REGISTRY.with_borrow_mut(|r| r.claim_active())
+    }
+
+    fn duration_since_start(&self) -> Duration {
+        Self::now()
+    }
+
+    fn debug_log(&self, arguments: std::fmt::Arguments<'_>) {
+        logging::debug(logging::SLINT, arguments);
+    }
+
+    fn set_clipboard_text(&self, text: &str, clipboard: Clipboard) {
+        Self::CLIPBOARD.with_borrow_mut(|c| c.set_text(text, clipboard));
+    }
+
+    fn clipboard_text(&self, clipboard: Clipboard) -> Option<String> {
+        Self::CLIPBOARD.with_borrow_mut(|c| c.text(clipboard))
+    }
+
+    // Slint keeps the first proxy for the whole process and refuses to set the platform of
+    // another thread if it is handed a second one. The proxy is not bound to any thread or window,
+    // it looks them up when it is called.
+    fn new_event_loop_proxy(&self) -> Option<Box<dyn EventLoopProxy>> {
+        static CREATED: AtomicBool = AtomicBool::new(false);
+        match CREATED.swap(true, Ordering::AcqRel) {
+            true => None,
+            false => Some(Box::new(EmbeddedEventLoopProxy)),
+        }
+    }
+}
+
+// ---------- EmbeddedClock ---------- //
+
+// The time source of Slint's timers and animations on the current thread. A manual clock only
+// moves through `EmbeddedHeadlessWindow::advance_time`, which makes animations deterministic.
+#[cfg(feature = "software")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmbeddedClock {
+    Real,
+    Manual,
+}
+
+// Only headless windows can switch to the manual clock.
+enum EmbeddedClockState {
+    Real(Instant),
+    #[cfg(feature = "software")]
+    Manual(Duration),
+}
+
+// ---------- EmbeddedEventLoopProxy ---------- //
+
+enum EmbeddedEvent {
+    Invoke(Box<dyn FnOnce() + Send>),
+    Quit(EmbeddedWindowId),
+}
+
+// The event queues of the threads with open windows.
+static EVENT_LOOPS: Mutex<Vec<(ThreadId, Sender<EmbeddedEvent>)>> = Mutex::new(Vec::new());
+
+struct EmbeddedEventLoopProxy;
+
+impl EmbeddedEventLoopProxy {
+    // The queue of the calling thread if it has windows. Other threads are only served while
+    // there is a single window thread, otherwise the closure could end up on the thread of another
+    // editor. `EmbeddedWindowProxy` reaches a specific editor from any thread.
+    fn sender() -> Result<Sender<EmbeddedEvent>, EventLoopError> {
+        let thread_id = std::thread::current().id();
+        let event_loops = EVENT_LOOPS.lock().unwrap();
+        if let Some((_, sender)) = event_loops.iter().find(|(t, _)| *t == thread_id) {
+            return Ok(sender.clone());
+        }
+        match event_loops.as_slice() {
+            [] => Err(EventLoopError::EventLoopTerminated),
+            [(_, sender)] => Ok(sender.clone()),
+            _ => {
+                logging::warn(
+                    logging::WINDOW,
+                    "Event loop is ambiguous with several window threads, use EmbeddedWindowProxy",
+                );
+                Err(EventLoopError::NoEventLoopProvider)
+            }
+        }
+    }
+}
+
+impl EventLoopProxy for EmbeddedEventLoopProxy {
+    // Closes the window which is active on the calling thread, i.e. the one whose callback quits.
+    // Other editors on the thread stay open.
+    fn quit_event_loop(&self) -> Result<(), EventLoopError> {
+        let window_id = EmbeddedPlatform::WINDOW_REGISTRY
+            .try_with(|r| r.borrow().active.last().copied())
+            .ok()
+            .flatten();
+        let Some(window_id) = window_id else {
+            logging::warn(
+                logging::WINDOW,
+                "Quit outside of an embedded window ignored",
+            );
+            return Ok(());
+        };
+        Self::sender()?
+            .send(EmbeddedEvent::Quit(window_id))
+            .map_err(|_| EventLoopError::EventLoopTerminated)
+    }
+
+    fn invoke_from_event_loop(
+        &self,
+        event: Box<dyn FnOnce() + Send>,
+    ) -> Result<(), EventLoopError> {
+        Self::sender()?
+            .send(EmbeddedEvent::Invoke(event))
+            .map_err(|_| EventLoopError::EventLoopTerminated)
+    }
+}
+
+// ---------- EmbeddedWindowRegistry ---------- //
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct EmbeddedWindowId(u64);
+
+#[derive(Default)]
+struct EmbeddedWindowRegistry {
+    next_id: u64,
+    windows: HashMap<EmbeddedWindowId, EmbeddedWindowEntry>,
+    active: Vec<EmbeddedWindowId>,
+}
+
+struct EmbeddedWindowEntry {
+    window_adapter: Rc<EmbeddedWindowAdapter>,
+    claimed: bool,
+}
+
+impl EmbeddedWindowRegistry {
+    fn register(
+        &mut self,
+        window_adapter: Rc<EmbeddedWindowAdapter>,
+        claimed: bool,
+    ) -> EmbeddedWindowId {
+        let id = EmbeddedWindowId(self.next_id);
+        self.next_id += 1;
+        self.windows.insert(
+            id,
+            EmbeddedWindowEntry {
+                window_adapter,
+                claimed,
+            },
+        );
+        id
+    }
+
+    fn unregister(&mut self, id: EmbeddedWindowId) -> Option<Rc<EmbeddedWindowAdapter>> {
+        self.active.retain(|a| *a != id);
+        self.windows.remove(&id).map(|e| e.window_adapter)
+    }
+
+    fn window_adapter(&self, id: EmbeddedWindowId) -> Option<Rc<EmbeddedWindowAdapter>> {
+        self.windows.get(&id).map(|e| e.window_adapter.clone())
+    }
+
+    fn claim_active(&mut self) -> Result<Rc<dyn WindowAdapter>, PlatformError> {
+        let Some(id) = self.active.last() else {
+            return Err(PlatformError::Other("No active embedded window".into()));
+        };
+        let Some(entry) = self.windows.get_mut(id) else {
+            return Err(PlatformError::Other(format!(
+                "Embedded window {id:?} is not registered"
+            )));
+        };
+        if entry.claimed {
+            return Err(PlatformError::Other(format!(
+                "Embedded window {id:?} already has a component"
+            )));
+        }
+        entry.claimed = true;
+        Ok(entry.window_adapter.clone() as _)
+    }
+}
+
+struct ActiveWindowGuard(EmbeddedWindowId);
+
+impl Drop for ActiveWindowGuard {
+    fn drop(&mut self) {
+        let _ = EmbeddedPlatform::WINDOW_REGISTRY.try_with(|r| {
+            let mut r = r.borrow_mut();
+            if let Some(pos) = r.active.iter().rposition(|a| *a == self.0) {
+                r.active.remove(pos);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_window() -> Option<EmbeddedWindowId> {
+        EmbeddedPlatform::WINDOW_REGISTRY.with_borrow(|r| r.active.last().copied())
+    }
+
+    fn claim() -> Result<Rc<dyn WindowAdapter>, PlatformError> {
+        EmbeddedPlatform::default().create_window_adapter()
+    }
+
+    fn is_same(
+        claimed: &Rc<dyn WindowAdapter>,
+        window_adapter: &Rc<EmbeddedWindowAdapter>,
+    ) -> bool {
+        std::ptr::addr_eq(Rc::as_ptr(claimed), Rc::as_ptr(window_adapter))
+    }
+
+    #[test]
+    fn interleaved_register_and_unregister() {
+        let a = EmbeddedPlatform::register_window(EmbeddedWindowAdapter::new_for_test(Vec::new()));
+        let b = EmbeddedPlatform::register_window(EmbeddedWindowAdapter::new_for_test(Vec::new()));
+        let c = EmbeddedPlatform::register_window(EmbeddedWindowAdapter::new_for_test(Vec::new()));
+        assert!(EmbeddedPlatform::unregister_window(b).is_some());
+
+        let d = EmbeddedPlatform::register_window(EmbeddedWindowAdapter::new_for_test(Vec::new()));
+        assert!(a != b && b != c && c != d && d != b);
+        assert!(EmbeddedPlatform::window_adapter(a).is_some());
+        assert!(EmbeddedPlatform::window_adapter(b).is_none());
+        assert!(EmbeddedPlatform::window_adapter(c).is_some());
+        assert!(EmbeddedPlatform::window_adapter(d).is_some());
+
+        assert!(EmbeddedPlatform::unregister_window(a).is_some());
+        assert!(EmbeddedPlatform::unregister_window(a).is_none());
+        assert!(EmbeddedPlatform::window_adapter(c).is_some());
+        assert!(EmbeddedPlatform::unregister_window(d).is_some());
+        assert!(EmbeddedPlatform::unregister_window(c).is_some());
+        assert!(EmbeddedPlatform::WINDOW_REGISTRY.with_borrow(|r| r.windows.is_empty()));
+    }
+
+    #[test]
+    fn nested_active_scopes() {
+        let a_adapter = EmbeddedWindowAdapter::new_for_test(Vec::new());
+        let b_adapter = EmbeddedWindowAdapter::new_for_test(Vec::new());
+        let a = EmbeddedPlatform::register_window(a_adapter.clone());
+        let b = EmbeddedPlatform::register_window(b_adapter.clone());
+
+        assert!(claim().is_err());
+        EmbeddedPlatform::with_active_window(a, || {
+            assert_eq!(active_window(), Some(a));
+            EmbeddedPlatform::with_active_window(b, || {
+                assert_eq!(active_window(), Some(b));
+                assert!(is_same(&claim().unwrap(), &b_adapter));
+            });
+            assert_eq!(active_window(), Some(a));
+            assert!(is_same(&claim().unwrap(), &a_adapter));
+        });
+        assert_eq!(active_window(), None);
+    }
+
+    #[test]
+    fn unregister_while_active() {
+        let a = EmbeddedPlatform::register_window(EmbeddedWindowAdapter::new_for_test(Vec::new()));
+        let b = EmbeddedPlatform::register_window(EmbeddedWindowAdapter::new_for_test(Vec::new()));
+
+        EmbeddedPlatform::with_active_window(a, || {
+            EmbeddedPlatform::with_active_window(b, || {
+                EmbeddedPlatform::unregister_window(b);
+                assert_eq!(active_window(), Some(a));
+            });
+            assert_eq!(active_window(), Some(a));
+        });
+        assert_eq!(active_window(), None);
+    }
+
+    #[test]
+    fn double_claim() {
+        let a = EmbeddedPlatform::register_window(EmbeddedWindowAdapter::new_for_test(Vec::new()));
+        let popup = EmbeddedPlatform::register_popup_window(EmbeddedWindowAdapter::new_for_test(
+            Vec::new(),
+        ));
+
+        EmbeddedPlatform::with_active_window(a, || {
+            assert!(claim().is_ok());
+            assert!(claim().is_err());
+        });
+        // Popups are claimed by the component which opens them.
+        EmbeddedPlatform::with_active_window(popup, || assert!(claim().is_err()));
+    }
+
+    #[test]
+    fn proxy_with_two_window_threads() {
+        let (registered_sender, registered) = unbounded();
+        let (done_sender, done) = unbounded::<()>();
+
+        let threads: Vec<_> = (0..2)
+            .map(|_| {
+                let registered_sender = registered_sender.clone();
+                let done = done.clone();
+                std::thread::spawn(move || {
+                    let id = EmbeddedPlatform::register_window(
+                        EmbeddedWindowAdapter::new_for_test(Vec::new()),
+                    );
+
+                    // A window thread always reaches its own queue.
+                    let (thread_sender, thread) = unbounded();
+                    EmbeddedEventLoopProxy
+                        .invoke_from_event_loop(Box::new(move || {
+                            thread_sender.send(std::thread::current().id()).unwrap();
+                        }))
+                        .unwrap();
+                    EmbeddedPlatform::process_events();
+                    assert_eq!(thread.try_recv(), Ok(std::thread::current().id()));
+
+                    registered_sender.send(()).unwrap();
+                    let _ = done.recv();
+                    EmbeddedPlatform::unregister_window(id);
+                })
+            })
+            .collect();
+        drop(registered_sender);
+
+        registered.recv().unwrap();
+        registered.recv().unwrap();
+        // Other tests may have window threads as well, which keeps it ambiguous.
+        assert!(matches!(
+            EmbeddedEventLoopProxy.invoke_from_event_loop(Box::new(|| {})),
+            Err(EventLoopError::NoEventLoopProvider)
+        ));
+
+        drop(done_sender);
+        for thread in threads {
+            thread.join().unwrap();
+        }
     }
 }
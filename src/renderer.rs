@@ -4,55 +4,129 @@ use raw_window_handle::{
 };
 use std::sync::Arc;
 
+use crate::{
+    EmbeddedRendererType,
+    error::{EmbeddedRendererError, EmbeddedRendererErrorKind},
+};
+
+#[cfg(feature = "femtovg")]
+use crate::{EmbeddedSwapInterval, FemtoVGApi, FemtoVGOptions, logging};
 #[cfg(feature = "femtovg")]
 use glutin::{
-    config::ConfigTemplate,
-    context::{ContextAttributesBuilder, PossiblyCurrentContext, PossiblyCurrentGlContext},
+    config::{ConfigTemplateBuilder, GlConfig},
+    context::{
+        ContextApi, ContextAttributesBuilder, PossiblyCurrentContext, PossiblyCurrentGlContext,
+        Version,
+    },
     display::{Display, DisplayApiPreference, GetGlDisplay},
     prelude::{GlDisplay, NotCurrentGlContext},
-    surface::{GlSurface, SurfaceAttributesBuilder, WindowSurface},
+    surface::{GlSurface, SurfaceAttributesBuilder, SwapInterval, WindowSurface},
 };
 #[cfg(feature = "femtovg")]
 use i_slint_renderer_femtovg::{
     FemtoVGOpenGLRenderer, FemtoVGOpenGLRendererExt, FemtoVGRendererExt, opengl::OpenGLInterface,
 };
 
+#[cfg(feature = "skia")]
+use crate::{SkiaOptions, SkiaSurface};
 #[cfg(feature = "skia")]
 use i_slint_renderer_skia::{SkiaRenderer, SkiaSharedContext};
 
 #[cfg(feature = "software")]
 use bytemuck::{AnyBitPattern, NoUninit, Zeroable, cast_slice_mut};
 #[cfg(feature = "software")]
-use i_slint_renderer_software::{PremultipliedRgbaColor, SoftwareRenderer, TargetPixel};
+use i_slint_core::{
+    api::PhysicalSize,
+    graphics::{Rgba8Pixel, SharedPixelBuffer},
+};
+#[cfg(feature = "software")]
+use i_slint_renderer_software::{
+    PremultipliedRgbaColor, RepaintBufferType, SoftwareRenderer, TargetPixel,
+};
 #[cfg(feature = "software")]
 use softbuffer::Context;
+#[cfg(feature = "femtovg")]
+use std::cell::Cell;
 #[cfg(feature = "software")]
-use std::{cell::RefCell, ops::DerefMut};
+use std::{cell::RefCell, ops::DerefMut, rc::Rc};
 
 // ---------- EmbeddedRendererAdapter ---------- //
 
 pub(crate) trait EmbeddedRendererAdapter {
     fn set_window(
         &self,
-        baseview_window: &baseview::Window,
+        window_wrapper: &Arc<BaseviewWindowWrapper>,
         slint_window: &Window,
-    ) -> Result<(), String>;
-    fn render(&self, slint_window: &Window) -> Result<(), String>;
+    ) -> Result<(), EmbeddedRendererError>;
+    fn render(&self, slint_window: &Window) -> Result<(), EmbeddedRendererError>;
     fn renderer(&self) -> &dyn Renderer;
 }
 
+// Tried in order by `EmbeddedWindowAdapter::set_window` until one accepts the window.
+pub(crate) type EmbeddedRendererAdapters =
+    Vec<(EmbeddedRendererType, Box<dyn EmbeddedRendererAdapter>)>;
+
+pub(crate) fn create_renderer_adapters(
+    renderer_type: EmbeddedRendererType,
+) -> EmbeddedRendererAdapters {
+    match renderer_type {
+        EmbeddedRendererType::Auto => [
+            #[cfg(feature = "femtovg")]
+            EmbeddedRendererType::FemtoVG(Default::default()),
+            #[cfg(feature = "skia")]
+            EmbeddedRendererType::Skia(Default::default()),
+            #[cfg(feature = "software")]
+            EmbeddedRendererType::Software,
+        ]
+        .into_iter()
+        .flat_map(create_renderer_adapters)
+        .collect(),
+        #[cfg(feature = "femtovg")]
+        EmbeddedRendererType::FemtoVG(options) => vec![(
+            renderer_type,
+            Box::new(EmbeddedFemtoVGRendererAdapter::new(options))
+                as Box<dyn EmbeddedRendererAdapter>,
+        )],
+        #[cfg(feature = "skia")]
+        EmbeddedRendererType::Skia(options) => vec![(
+            renderer_type,
+            Box::new(EmbeddedSkiaRendererAdapter::new(options)) as Box<dyn EmbeddedRendererAdapter>,
+        )],
+        #[cfg(feature = "software")]
+        EmbeddedRendererType::Software => vec![(
+            renderer_type,
+            Box::new(EmbeddedSoftwareRendererAdapter::default())
+                as Box<dyn EmbeddedRendererAdapter>,
+        )],
+    }
+}
+
 // ---------- FemtoVG ---------- //
 
+#[cfg(feature = "femtovg")]
+const FEMTOVG: &str = "FemtoVG";
+
 #[cfg(feature = "femtovg")]
 pub(crate) struct EmbeddedFemtoVGRendererAdapter {
     renderer: FemtoVGOpenGLRenderer,
+    options: FemtoVGOptions,
 }
 
 #[cfg(feature = "femtovg")]
-impl Default for EmbeddedFemtoVGRendererAdapter {
-    fn default() -> Self {
+impl EmbeddedFemtoVGRendererAdapter {
+    fn new(options: FemtoVGOptions) -> Self {
         Self {
             renderer: FemtoVGOpenGLRenderer::new_suspended(),
+            options,
+        }
+    }
+
+    fn context_api(api: FemtoVGApi) -> ContextApi {
+        let version =
+            |version: Option<(u8, u8)>| version.map(|(major, minor)| Version::new(major, minor));
+        match api {
+            FemtoVGApi::OpenGL { version: v } => ContextApi::OpenGl(version(v)),
+            FemtoVGApi::Gles { version: v } => ContextApi::Gles(version(v)),
         }
     }
 }
@@ -61,14 +135,23 @@ impl Default for EmbeddedFemtoVGRendererAdapter {
 impl EmbeddedRendererAdapter for EmbeddedFemtoVGRendererAdapter {
     fn set_window(
         &self,
-        baseview_window: &baseview::Window,
+        window_wrapper: &Arc<BaseviewWindowWrapper>,
         slint_window: &Window,
-    ) -> Result<(), String> {
-        let raw_window_handle = baseview_window
+    ) -> Result<(), EmbeddedRendererError> {
+        // Checked by glutin in debug builds only.
+        if let Some(samples) = self.options.samples.filter(|s| !s.is_power_of_two()) {
+            return Err(EmbeddedRendererError::new(
+                FEMTOVG,
+                EmbeddedRendererErrorKind::Config,
+                format!("{samples} samples is not a power of two"),
+            ));
+        }
+
+        let raw_window_handle = window_wrapper
             .window_handle()
             .expect("No window handle")
             .as_raw();
-        let raw_display_handle = baseview_window
+        let raw_display_handle = window_wrapper
             .display_handle()
             .expect("No display handle")
             .as_raw();
@@ -82,45 +165,78 @@ impl EmbeddedRendererAdapter for EmbeddedFemtoVGRendererAdapter {
                 let display_api_preference = DisplayApiPreference::EglThenWgl(Some(raw_window_handle));
             }
         }
-        let display = unsafe { Display::new(raw_display_handle, display_api_preference) }
-            .map_err(|err| format!("FemtoVG display error: {err}"))?;
-
-        let config = unsafe { display.find_configs(ConfigTemplate::default()) }
-            .map_err(|err| format!("FemtoVG configs error: {err}"))?
-            .next();
+        let display = unsafe { Display::new(raw_display_handle, display_api_preference) }.map_err(
+            EmbeddedRendererError::map(FEMTOVG, EmbeddedRendererErrorKind::Display),
+        )?;
+
+        let mut template =
+            ConfigTemplateBuilder::new().with_transparency(self.options.transparency);
+        if let Some(samples) = self.options.samples {
+            template = template.with_multisampling(samples);
+        }
+        let mut configs = unsafe { display.find_configs(template.build()) }.map_err(
+            EmbeddedRendererError::map(FEMTOVG, EmbeddedRendererErrorKind::Config),
+        )?;
+        let config = match self.options.transparency {
+            true => configs.find(|c| c.supports_transparency().unwrap_or(false)),
+            false => configs.next(),
+        };
         let Some(config) = config else {
-            return Err("FemtoVG no config".into());
+            return Err(EmbeddedRendererError::new(
+                FEMTOVG,
+                EmbeddedRendererErrorKind::Config,
+                "No matching config",
+            ));
         };
 
-        let context_attributes = ContextAttributesBuilder::new().build(Some(raw_window_handle));
-        let context = unsafe { display.create_context(&config, &context_attributes) }
-            .map_err(|err| format!("FemtoVG context error: {err}"))?;
+        let mut context_attributes = ContextAttributesBuilder::new().with_debug(self.options.debug);
+        if let Some(api) = self.options.api {
+            context_attributes = context_attributes.with_context_api(Self::context_api(api));
+        }
+        let context_attributes = context_attributes.build(Some(raw_window_handle));
+        let context = unsafe { display.create_context(&config, &context_attributes) }.map_err(
+            EmbeddedRendererError::map(FEMTOVG, EmbeddedRendererErrorKind::Context),
+        )?;
 
         let size = slint_window.size();
-        let surface_attributes = SurfaceAttributesBuilder::<WindowSurface>::new().build(
-            raw_window_handle,
-            std::num::NonZeroU32::new(size.width).unwrap(),
-            std::num::NonZeroU32::new(size.height).unwrap(),
-        );
-        let surface = unsafe { display.create_window_surface(&config, &surface_attributes) }
-            .map_err(|err| format!("FemtoVG surface error: {err}"))?;
+        let surface_attributes = SurfaceAttributesBuilder::<WindowSurface>::new()
+            .with_srgb(self.options.srgb)
+            .build(
+                raw_window_handle,
+                std::num::NonZeroU32::new(size.width).unwrap(),
+                std::num::NonZeroU32::new(size.height).unwrap(),
+            );
+        let surface =
+            unsafe { display.create_window_surface(&config, &surface_attributes) }.map_err(
+                EmbeddedRendererError::map(FEMTOVG, EmbeddedRendererErrorKind::Surface),
+            )?;
+
+        let context = context
+            .make_current(&surface)
+            .map_err(EmbeddedRendererError::map(
+                FEMTOVG,
+                EmbeddedRendererErrorKind::Context,
+            ))?;
 
         self.renderer
-            .set_opengl_context(FemtoVGOpenGLInterface {
-                context: context
-                    .make_current(&surface)
-                    .map_err(|err| format!("FemtoVG current context error: {err}"))?,
+            .set_opengl_context(FemtoVGOpenGLInterface::new(
+                context,
                 surface,
-            })
-            .map_err(|err| format!("FemtoVG renderer error: {err}"))?;
+                self.options.swap_interval,
+            ))
+            .map_err(EmbeddedRendererError::map(
+                FEMTOVG,
+                EmbeddedRendererErrorKind::SetWindow,
+            ))?;
 
         Ok(())
     }
 
-    fn render(&self, _slint_window: &Window) -> Result<(), String> {
-        self.renderer
-            .render()
-            .map_err(|err| format!("FemtoVG render error: {err}"))
+    fn render(&self, _slint_window: &Window) -> Result<(), EmbeddedRendererError> {
+        self.renderer.render().map_err(EmbeddedRendererError::map(
+            FEMTOVG,
+            EmbeddedRendererErrorKind::Render,
+        ))
     }
 
     fn renderer(&self) -> &dyn Renderer {
@@ -128,10 +244,69 @@ impl EmbeddedRendererAdapter for EmbeddedFemtoVGRendererAdapter {
     }
 }
 
+#[cfg(feature = "femtovg")]
+thread_local! {
+    // Number of FemtoVG surfaces on this thread, for `EmbeddedSwapInterval::OnUnlessShared`.
+    static SURFACE_COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
 #[cfg(feature = "femtovg")]
 struct FemtoVGOpenGLInterface {
     context: PossiblyCurrentContext,
     surface: glutin::surface::Surface<WindowSurface>,
+    swap_interval: Option<EmbeddedSwapInterval>,
+    // Whether the surface currently waits for vsync, `None` if untouched.
+    vsync: Cell<Option<bool>>,
+}
+
+#[cfg(feature = "femtovg")]
+impl FemtoVGOpenGLInterface {
+    fn new(
+        context: PossiblyCurrentContext,
+        surface: glutin::surface::Surface<WindowSurface>,
+        swap_interval: Option<EmbeddedSwapInterval>,
+    ) -> Self {
+        SURFACE_COUNT.set(SURFACE_COUNT.get() + 1);
+        Self {
+            context,
+            surface,
+            swap_interval,
+            vsync: Cell::new(None),
+        }
+    }
+
+    // Re-evaluated before every swap, since `OnUnlessShared` depends on the surfaces open on this
+    // thread.
+    fn update_swap_interval(&self) {
+        let vsync = match self.swap_interval {
+            None => return,
+            Some(EmbeddedSwapInterval::On) => true,
+            Some(EmbeddedSwapInterval::Off) => false,
+            Some(EmbeddedSwapInterval::OnUnlessShared) => SURFACE_COUNT.get() == 1,
+        };
+        if self.vsync.replace(Some(vsync)) == Some(vsync) {
+            return;
+        }
+
+        let swap_interval = match vsync {
+            true => SwapInterval::Wait(std::num::NonZeroU32::MIN),
+            false => SwapInterval::DontWait,
+        };
+        // Not every platform supports this, so it is not worth failing over.
+        if let Err(err) = self.surface.set_swap_interval(&self.context, swap_interval) {
+            logging::warn(
+                logging::RENDERER,
+                format_args!("FemtoVG swap interval error: {err}"),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "femtovg")]
+impl Drop for FemtoVGOpenGLInterface {
+    fn drop(&mut self) {
+        SURFACE_COUNT.set(SURFACE_COUNT.get() - 1);
+    }
 }
 
 #[cfg(feature = "femtovg")]
@@ -143,6 +318,7 @@ unsafe impl OpenGLInterface for FemtoVGOpenGLInterface {
     }
 
     fn swap_buffers(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.update_swap_interval();
         self.surface
             .swap_buffers(&self.context)
             .map_err(|err| format!("FemotVG swap buffers error: {err}").into())
@@ -164,16 +340,27 @@ unsafe impl OpenGLInterface for FemtoVGOpenGLInterface {
 
 // ---------- Skia ---------- //
 
+#[cfg(feature = "skia")]
+const SKIA: &str = "Skia";
+
 #[cfg(feature = "skia")]
 pub(crate) struct EmbeddedSkiaRendererAdapter {
     renderer: SkiaRenderer,
 }
 
 #[cfg(feature = "skia")]
-impl Default for EmbeddedSkiaRendererAdapter {
-    fn default() -> Self {
+impl EmbeddedSkiaRendererAdapter {
+    fn new(options: SkiaOptions) -> Self {
+        let context = SkiaSharedContext::default();
         Self {
-            renderer: SkiaRenderer::default(&SkiaSharedContext::default()),
+            renderer: match options.surface {
+                SkiaSurface::Default => SkiaRenderer::default(&context),
+                #[cfg(feature = "skia-opengl")]
+                SkiaSurface::OpenGL => SkiaRenderer::default_opengl(&context),
+                #[cfg(feature = "skia-vulkan")]
+                SkiaSurface::Vulkan => SkiaRenderer::default_vulkan(&context),
+                SkiaSurface::Software => SkiaRenderer::default_software(&context),
+            },
         }
     }
 }
@@ -182,24 +369,27 @@ impl Default for EmbeddedSkiaRendererAdapter {
 impl EmbeddedRendererAdapter for EmbeddedSkiaRendererAdapter {
     fn set_window(
         &self,
-        baseview_window: &baseview::Window,
+        window_wrapper: &Arc<BaseviewWindowWrapper>,
         slint_window: &Window,
-    ) -> Result<(), String> {
-        let window_wrapper = Arc::new(BaseviewWindowWrapper::new(baseview_window));
+    ) -> Result<(), EmbeddedRendererError> {
         self.renderer
             .set_window_handle(
                 window_wrapper.clone(),
-                window_wrapper,
+                window_wrapper.clone(),
                 slint_window.size(),
                 None,
             )
-            .map_err(|err| format!("Skia set window error: {err}"))
+            .map_err(EmbeddedRendererError::map(
+                SKIA,
+                EmbeddedRendererErrorKind::SetWindow,
+            ))
     }
 
-    fn render(&self, _slint_window: &Window) -> Result<(), String> {
-        self.renderer
-            .render()
-            .map_err(|err| format!("Skia render error: {err}"))
+    fn render(&self, _slint_window: &Window) -> Result<(), EmbeddedRendererError> {
+        self.renderer.render().map_err(EmbeddedRendererError::map(
+            SKIA,
+            EmbeddedRendererErrorKind::Render,
+        ))
     }
 
     fn renderer(&self) -> &dyn Renderer {
@@ -209,6 +399,9 @@ impl EmbeddedRendererAdapter for EmbeddedSkiaRendererAdapter {
 
 // ---------- Software ---------- //
 
+#[cfg(feature = "software")]
+const SOFTWARE: &str = "Software";
+
 #[cfg(feature = "software")]
 #[derive(Default)]
 pub(crate) struct EmbeddedSoftwareRendererAdapter {
@@ -223,20 +416,22 @@ pub(crate) struct EmbeddedSoftwareRendererAdapter {
 impl EmbeddedRendererAdapter for EmbeddedSoftwareRendererAdapter {
     fn set_window(
         &self,
-        baseview_window: &baseview::Window,
+        window_wrapper: &Arc<BaseviewWindowWrapper>,
         _slint_window: &Window,
-    ) -> Result<(), String> {
-        let window_wrapper = Arc::new(BaseviewWindowWrapper::new(baseview_window));
-        let context = Context::new(window_wrapper.clone())
-            .map_err(|err| format!("Software context error: {err}"))?;
-        let surface = softbuffer::Surface::new(&context, window_wrapper)
-            .map_err(|err| format!("Software surface error: {err}"))?;
+    ) -> Result<(), EmbeddedRendererError> {
+        let context = Context::new(window_wrapper.clone()).map_err(EmbeddedRendererError::map(
+            SOFTWARE,
+            EmbeddedRendererErrorKind::Context,
+        ))?;
+        let surface = softbuffer::Surface::new(&context, window_wrapper.clone()).map_err(
+            EmbeddedRendererError::map(SOFTWARE, EmbeddedRendererErrorKind::Surface),
+        )?;
         self.context.borrow_mut().replace(context);
         self.surface.borrow_mut().replace(surface);
         Ok(())
     }
 
-    fn render(&self, slint_window: &Window) -> Result<(), String> {
+    fn render(&self, slint_window: &Window) -> Result<(), EmbeddedRendererError> {
         let mut surface = self.surface.borrow_mut();
         let Some(surface) = surface.as_mut() else {
             return Ok(());
@@ -248,17 +443,87 @@ impl EmbeddedRendererAdapter for EmbeddedSoftwareRendererAdapter {
                 std::num::NonZeroU32::new(size.width).unwrap(),
                 std::num::NonZeroU32::new(size.height).unwrap(),
             )
-            .map_err(|err| format!("Software resize error: {err}"))?;
-
-        let mut buffer = surface
-            .buffer_mut()
-            .map_err(|err| format!("Software buffer error: {err}"))?;
+            .map_err(EmbeddedRendererError::map(
+                SOFTWARE,
+                EmbeddedRendererErrorKind::Resize,
+            ))?;
+
+        let mut buffer = surface.buffer_mut().map_err(EmbeddedRendererError::map(
+            SOFTWARE,
+            EmbeddedRendererErrorKind::Buffer,
+        ))?;
+
+        // The age is the number of presents since the buffer was last shown, so Slint only has
+        // to repaint what changed since then. Unknown contents need a full repaint.
+        self.renderer.set_repaint_buffer_type(match buffer.age() {
+            1 => RepaintBufferType::ReusedBuffer,
+            2 => RepaintBufferType::SwappedBuffers,
+            _ => RepaintBufferType::NewBuffer,
+        });
 
         let soft_buffer: &mut [SoftBufferPixel] = cast_slice_mut(buffer.deref_mut());
-        self.renderer.render(soft_buffer, size.width as _);
+        let region = self.renderer.render(soft_buffer, size.width as _);
+
+        let damage: Vec<_> = region
+            .iter()
+            .filter_map(|(position, size)| {
+                Some(softbuffer::Rect {
+                    x: position.x.max(0) as _,
+                    y: position.y.max(0) as _,
+                    width: std::num::NonZeroU32::new(size.width)?,
+                    height: std::num::NonZeroU32::new(size.height)?,
+                })
+            })
+            .collect();
         buffer
-            .present()
-            .map_err(|err| format!("Software present error: {err}"))
+            .present_with_damage(&damage)
+            .map_err(EmbeddedRendererError::map(
+                SOFTWARE,
+                EmbeddedRendererErrorKind::Present,
+            ))
+    }
+
+    fn renderer(&self) -> &dyn Renderer {
+        &self.renderer
+    }
+}
+
+// Renders into memory instead of a window, for tests and thumbnails.
+#[cfg(feature = "software")]
+#[derive(Default)]
+pub(crate) struct EmbeddedHeadlessRendererAdapter {
+    renderer: SoftwareRenderer,
+    buffer: Rc<RefCell<EmbeddedHeadlessBuffer>>,
+}
+
+#[cfg(feature = "software")]
+impl EmbeddedHeadlessRendererAdapter {
+    pub(crate) fn buffer(&self) -> Rc<RefCell<EmbeddedHeadlessBuffer>> {
+        self.buffer.clone()
+    }
+}
+
+#[cfg(feature = "software")]
+impl EmbeddedRendererAdapter for EmbeddedHeadlessRendererAdapter {
+    fn set_window(
+        &self,
+        _window_wrapper: &Arc<BaseviewWindowWrapper>,
+        _slint_window: &Window,
+    ) -> Result<(), EmbeddedRendererError> {
+        Ok(())
+    }
+
+    fn render(&self, slint_window: &Window) -> Result<(), EmbeddedRendererError> {
+        let size = slint_window.size();
+        let mut buffer = self.buffer.borrow_mut();
+        buffer.size = size;
+        buffer.pixels.clear();
+        buffer.pixels.resize(
+            size.width as usize * size.height as usize,
+            PremultipliedRgbaColor::background(),
+        );
+        self.renderer.render(&mut buffer.pixels, size.width as _);
+        Ok(())
     }
 
     fn renderer(&self) -> &dyn Renderer {
@@ -266,6 +531,34 @@ impl EmbeddedRendererAdapter for EmbeddedSoftwareRendererAdapter {
     }
 }
 
+#[cfg(feature = "software")]
+#[derive(Default)]
+pub(crate) struct EmbeddedHeadlessBuffer {
+    size: PhysicalSize,
+    pixels: Vec<PremultipliedRgbaColor>,
+}
+
+#[cfg(feature = "software")]
+impl EmbeddedHeadlessBuffer {
+    pub(crate) fn to_rgba(&self) -> SharedPixelBuffer<Rgba8Pixel> {
+        let unpremultiply = |c: u8, a: u8| match a {
+            0 => 0,
+            _ => ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8,
+        };
+
+        let mut rgba = SharedPixelBuffer::new(self.size.width, self.size.height);
+        for (dst, src) in rgba.make_mut_slice().iter_mut().zip(&self.pixels) {
+            *dst = Rgba8Pixel::new(
+                unpremultiply(src.red, src.alpha),
+                unpremultiply(src.green, src.alpha),
+                unpremultiply(src.blue, src.alpha),
+                src.alpha,
+            );
+        }
+        rgba
+    }
+}
+
 #[cfg(feature = "software")]
 #[derive(Clone, Copy, Zeroable)]
 struct SoftBufferPixel(u32);
@@ -326,12 +619,23 @@ pub(crate) struct BaseviewWindowWrapper {
 }
 
 impl BaseviewWindowWrapper {
-    fn new<'a>(window: &baseview::Window<'a>) -> Self {
+    pub(crate) fn new<'a>(window: &baseview::Window<'a>) -> Self {
         Self {
             raw_display_handle: window.display_handle().expect("No display handle").as_raw(),
             raw_window_handle: window.window_handle().expect("No window handle").as_raw(),
         }
     }
+
+    #[cfg(test)]
+    pub(crate) fn from_raw(
+        raw_display_handle: raw_window_handle::RawDisplayHandle,
+        raw_window_handle: raw_window_handle::RawWindowHandle,
+    ) -> Self {
+        Self {
+            raw_display_handle,
+            raw_window_handle,
+        }
+    }
 }
 
 impl HasDisplayHandle for BaseviewWindowWrapper {
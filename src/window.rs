@@ -1,42 +1,124 @@
-use crossbeam_channel::Receiver;
-use i_slint_core::{api::LogicalSize, platform::set_platform};
+use crossbeam_channel::{Receiver, Sender, bounded, unbounded};
+use i_slint_core::{
+    api::{LogicalSize, PhysicalSize, Window, WindowSize},
+    platform::update_timers_and_animations,
+    window::WindowAdapter,
+};
 use raw_window_handle::{HandleError, HasWindowHandle, WindowHandle};
-use std::{error::Error, path::Path, rc::Rc, sync::Arc};
+use std::{
+    any::{Any, TypeId},
+    error::Error,
+    marker::PhantomData,
+    path::Path,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
 
-use crate::{platform::EmbeddedPlatform, window_adapter::EmbeddedWindowAdapter};
+use crate::{
+    EmbeddedImeEvent, EmbeddedInputMethod,
+    channel::{EmbeddedSender, EmbeddedViewChannel},
+    error::EmbeddedError,
+    logging,
+    platform::{EmbeddedPlatform, EmbeddedWindowId},
+    renderer::create_renderer_adapters,
+    window_adapter::EmbeddedWindowAdapter,
+};
 
 // ---------- EmbeddedWindow ---------- //
 
+// `Auto` tries FemtoVG, Skia and Software in this order, skipping disabled features.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EmbeddedRendererType {
+    Auto,
     #[cfg(feature = "femtovg")]
-    FemtoVG,
+    FemtoVG(FemtoVGOptions),
     #[cfg(feature = "skia")]
-    Skia,
+    Skia(SkiaOptions),
     #[cfg(feature = "software")]
     Software,
 }
 
+// `None` leaves the choice to the driver.
+#[cfg(feature = "femtovg")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FemtoVGOptions {
+    // Number of samples for multisample anti-aliasing, a power of two.
+    pub samples: Option<u8>,
+    pub srgb: Option<bool>,
+    pub api: Option<FemtoVGApi>,
+    pub debug: bool,
+    pub swap_interval: Option<EmbeddedSwapInterval>,
+    pub transparency: bool,
+}
+
+// The version is `(major, minor)`, `None` picks the latest available one.
+#[cfg(feature = "femtovg")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FemtoVGApi {
+    OpenGL { version: Option<(u8, u8)> },
+    Gles { version: Option<(u8, u8)> },
+}
+
+// `OnUnlessShared` only waits for vsync while the window is the only FemtoVG window on its thread,
+// since blocking on every swap serializes the windows of a thread, e.g. multiple editors on the
+// host's GUI thread. baseview gives every editor its own thread on X11, where it acts like `On`.
+// This is not adaptive vsync, late swaps never tear.
+#[cfg(feature = "femtovg")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmbeddedSwapInterval {
+    On,
+    Off,
+    OnUnlessShared,
+}
+
+// `Default` leaves the choice of the surface to Skia.
+#[cfg(feature = "skia")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SkiaSurface {
+    #[default]
+    Default,
+    #[cfg(feature = "skia-opengl")]
+    OpenGL,
+    #[cfg(feature = "skia-vulkan")]
+    Vulkan,
+    // Skia's CPU raster, for hosts with broken drivers.
+    Software,
+}
+
+#[cfg(feature = "skia")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SkiaOptions {
+    pub surface: SkiaSurface,
+}
+
 pub struct EmbeddedWindow {
     window_handle: baseview::WindowHandle,
+    command_sender: Sender<EmbeddedWindowCommand>,
+    size_constraints: Arc<Mutex<EmbeddedSizeConstraints>>,
+    view_type: TypeId,
 }
 
 impl EmbeddedWindow {
-    pub fn new<B, M, V>(
+    pub fn new<B, M, O, V>(
         parent: impl HasWindowHandle,
         title: String,
         size: LogicalSize,
         user_scale_factor: f32,
         system_scale_policy: baseview::WindowScalePolicy,
         renderer_type: EmbeddedRendererType,
-        receiver: Arc<Receiver<M>>,
+        channel: EmbeddedViewChannel<M, O>,
         build: B,
-    ) -> Self
+    ) -> Result<Self, EmbeddedError>
     where
-        B: Fn(EmbeddedWindowInterface) -> V + Send + 'static,
+        B: Fn(EmbeddedWindowInterface<O>) -> V + Send + 'static,
         M: Send + 'static,
+        O: Send + 'static,
         V: EmbeddedView<M> + 'static,
     {
-        let window_handle = baseview::Window::open_parented(
+        // The build closure runs on the window thread, which is not necessarily this one.
+        let (result_sender, result_receiver) = bounded(1);
+        let (command_sender, command_receiver) = unbounded();
+        let mut window_handle = baseview::Window::open_parented(
             parent,
             baseview::WindowOpenOptions {
                 title,
@@ -47,31 +129,57 @@ impl EmbeddedWindow {
                 scale: system_scale_policy,
             },
             move |baseview_window| {
-                let _ = set_platform(Box::new(EmbeddedPlatform::default()));
+                let result = EmbeddedPlatform::install().map_err(EmbeddedError::from);
 
                 let window_adapter = EmbeddedWindowAdapter::new(
                     size,
                     user_scale_factor,
                     system_scale_policy,
-                    renderer_type,
+                    create_renderer_adapters(renderer_type),
                 );
-                EmbeddedPlatform::WINDOW_ADAPTER_INNER
-                    .with_borrow_mut(|a| a.replace(window_adapter.clone()));
-                window_adapter.set_window(baseview_window);
+                let result = result.and_then(|()| {
+                    window_adapter
+                        .set_window(baseview_window)
+                        .map_err(EmbeddedError::from)
+                });
+                let window_id = EmbeddedPlatform::register_window(window_adapter.clone());
 
-                let interface = EmbeddedWindowInterface {
-                    window_adapter: window_adapter.clone(),
-                };
+                // Without a platform or renderer there is nothing to build, the window is closed by
+                // `new`.
+                let view = result.is_ok().then(|| {
+                    let interface =
+                        EmbeddedWindowInterface::new(window_adapter.clone(), channel.sender);
+                    EmbeddedPlatform::with_active_window(window_id, || build(interface))
+                });
+                let _ =
+                    result_sender.send(result.map(|()| window_adapter.shared_size_constraints()));
 
                 EmbeddedWindowHandler {
-                    receiver,
-                    view: build(interface),
+                    command_receiver,
+                    receiver: channel.receiver,
+                    view,
                     window_adapter,
+                    window_id,
                 }
             },
         );
 
-        Self { window_handle }
+        match result_receiver.recv() {
+            Ok(Ok(size_constraints)) => Ok(Self {
+                window_handle,
+                command_sender,
+                size_constraints,
+                view_type: TypeId::of::<V>(),
+            }),
+            Ok(Err(err)) => {
+                window_handle.close();
+                Err(err)
+            }
+            Err(_) => {
+                window_handle.close();
+                Err(EmbeddedError::WindowNotOpened)
+            }
+        }
     }
 
     pub fn close(&mut self) {
@@ -81,6 +189,37 @@ impl EmbeddedWindow {
     pub fn is_open(&self) -> bool {
         self.window_handle.is_open()
     }
+
+    // `V` has to be the type of the view returned by the build closure.
+    pub fn proxy<V: 'static>(&self) -> Result<EmbeddedWindowProxy<V>, EmbeddedError> {
+        if TypeId::of::<V>() != self.view_type {
+            return Err(EmbeddedError::ViewTypeMismatch);
+        }
+        Ok(EmbeddedWindowProxy {
+            command_sender: self.command_sender.clone(),
+            view: PhantomData,
+        })
+    }
+
+    // Updated by the window thread whenever the layout of the view changes.
+    pub fn size_constraints(&self) -> EmbeddedSizeConstraints {
+        *self.size_constraints.lock().unwrap()
+    }
+
+    // Safe to call from the host thread, the resize is applied with the next frame. `size` is the
+    // window size, i.e. the logical size of the view times the user scale factor, which is also
+    // what the resize callback reports.
+    pub fn resize(&self, size: LogicalSize) {
+        let _ = self
+            .command_sender
+            .send(EmbeddedWindowCommand::Resize(WindowSize::Logical(size)));
+    }
+
+    pub fn set_physical_size(&self, size: PhysicalSize) {
+        let _ = self
+            .command_sender
+            .send(EmbeddedWindowCommand::Resize(WindowSize::Physical(size)));
+    }
 }
 
 impl HasWindowHandle for EmbeddedWindow {
@@ -89,6 +228,106 @@ impl HasWindowHandle for EmbeddedWindow {
     }
 }
 
+// ---------- EmbeddedWindowProxy ---------- //
+
+// Reaches the editor of one window from any thread. Slint's `invoke_from_event_loop` can't tell
+// editors on different threads apart when called from a thread without windows, and fails then.
+pub struct EmbeddedWindowProxy<V> {
+    command_sender: Sender<EmbeddedWindowCommand>,
+    view: PhantomData<fn() -> V>,
+}
+
+impl<V: 'static> EmbeddedWindowProxy<V> {
+    // Runs `f` on the window thread with the next frame.
+    pub fn invoke(
+        &self,
+        f: impl FnOnce(&V, &Window) + Send + 'static,
+    ) -> Result<(), EmbeddedError> {
+        self.command_sender
+            .send(EmbeddedWindowCommand::Invoke(Box::new(
+                move |view, window| match view.downcast_ref::<V>() {
+                    Some(view) => f(view, window),
+                    // Checked by `EmbeddedWindow::proxy` already.
+                    None => logging::error(logging::WINDOW, EmbeddedError::ViewTypeMismatch),
+                },
+            )))
+            .map_err(|_| EmbeddedError::WindowClosed)
+    }
+}
+
+impl<V> Clone for EmbeddedWindowProxy<V> {
+    fn clone(&self) -> Self {
+        Self {
+            command_sender: self.command_sender.clone(),
+            view: PhantomData,
+        }
+    }
+}
+
+// ---------- EmbeddedSizeConstraints ---------- //
+
+// Sizes are window sizes, like the one passed to `EmbeddedWindow::resize`. Slint has no notion of
+// an aspect ratio (width / height), so it is set by the view.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EmbeddedSizeConstraints {
+    pub min: Option<LogicalSize>,
+    pub max: Option<LogicalSize>,
+    pub preferred: LogicalSize,
+    pub aspect_ratio: Option<f32>,
+}
+
+impl EmbeddedSizeConstraints {
+    pub fn is_resizable(&self) -> bool {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => min != max,
+            _ => true,
+        }
+    }
+
+    // Snaps a size proposed by the host to the closest valid one. The width is kept if the
+    // aspect ratio requires changing a dimension.
+    pub fn check_size(&self, size: LogicalSize) -> LogicalSize {
+        let clamp_width = |width: f32| {
+            let width = self.max.map_or(width, |max| width.min(max.width));
+            self.min.map_or(width, |min| width.max(min.width))
+        };
+        let clamp_height = |height: f32| {
+            let height = self.max.map_or(height, |max| height.min(max.height));
+            self.min.map_or(height, |min| height.max(min.height))
+        };
+
+        let width = clamp_width(size.width);
+        match self.aspect_ratio {
+            Some(aspect_ratio) if aspect_ratio > 0.0 => {
+                let height = clamp_height(width / aspect_ratio);
+                LogicalSize::new(clamp_width(height * aspect_ratio), height)
+            }
+            _ => LogicalSize::new(width, clamp_height(size.height)),
+        }
+    }
+}
+
+// ---------- EmbeddedFrameCounters ---------- //
+
+// `frames` counts every frame tick, `rendered_frames` only those which actually rendered. An idle
+// view keeps `rendered_frames` constant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EmbeddedFrameCounters {
+    pub frames: u64,
+    pub rendered_frames: u64,
+}
+
+// ---------- EmbeddedFramePacing ---------- //
+
+// `Host` renders with every frame tick of baseview, `MaxFps` skips ticks to stay below the given
+// rate. Only windows are paced, headless windows render whenever a frame is advanced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmbeddedFramePacing {
+    #[default]
+    Host,
+    MaxFps(u32),
+}
+
 // ---------- EmbeddedView ---------- //
 
 pub trait EmbeddedView<M: Send> {
@@ -97,11 +336,48 @@ pub trait EmbeddedView<M: Send> {
 
 // ---------- EmbeddedWindowInterface ---------- //
 
-pub struct EmbeddedWindowInterface {
+pub struct EmbeddedWindowInterface<O = ()> {
     window_adapter: Rc<EmbeddedWindowAdapter>,
+    sender: EmbeddedSender<O>,
 }
 
-impl EmbeddedWindowInterface {
+impl<O> EmbeddedWindowInterface<O> {
+    pub(crate) fn new(
+        window_adapter: Rc<EmbeddedWindowAdapter>,
+        sender: EmbeddedSender<O>,
+    ) -> Self {
+        Self {
+            window_adapter,
+            sender,
+        }
+    }
+
+    pub fn sender(&self) -> &EmbeddedSender<O> {
+        &self.sender
+    }
+
+    // The renderer which was chosen, never `Auto`.
+    pub fn renderer_type(&self) -> EmbeddedRendererType {
+        self.window_adapter.renderer_type()
+    }
+
+    pub fn frame_counters(&self) -> EmbeddedFrameCounters {
+        self.window_adapter.frame_counters()
+    }
+
+    // Polled on the window thread with every frame in which it was woken.
+    pub fn spawn_local(&self, future: impl Future<Output = ()> + 'static) {
+        self.window_adapter.spawn_local(future);
+    }
+
+    pub fn set_aspect_ratio(&self, aspect_ratio: Option<f32>) {
+        self.window_adapter.set_aspect_ratio(aspect_ratio);
+    }
+
+    pub fn set_frame_pacing(&self, frame_pacing: EmbeddedFramePacing) {
+        self.window_adapter.set_frame_pacing(frame_pacing);
+    }
+
     pub fn register_font_from_memory(&self, data: &'static [u8]) -> Result<(), Box<dyn Error>> {
         self.window_adapter
             .renderer()
@@ -115,23 +391,67 @@ impl EmbeddedWindowInterface {
     pub fn set_user_scale_factor(&self, user_scale_factor: f32) {
         self.window_adapter.set_user_scale_factor(user_scale_factor);
     }
+
+    // Called on the window thread with the new window size, i.e. the logical size times the user
+    // scale factor, whenever the view resizes itself.
+    pub fn set_resize_callback(&self, resize_callback: Option<Box<dyn Fn(LogicalSize)>>) {
+        self.window_adapter.set_resize_callback(resize_callback);
+    }
+
+    pub fn set_input_method(&self, input_method: Option<Box<dyn EmbeddedInputMethod>>) {
+        self.window_adapter.set_input_method(input_method);
+    }
+
+    pub fn dispatch_ime_event(&self, event: EmbeddedImeEvent) {
+        self.window_adapter.dispatch_ime_event(event);
+    }
 }
 
 // ---------- EmbeddedWindowHandler ---------- //
 
+// Sent by `EmbeddedWindow` and `EmbeddedWindowProxy` from other threads.
+enum EmbeddedWindowCommand {
+    Resize(WindowSize),
+    Invoke(Box<dyn FnOnce(&dyn Any, &Window) + Send>),
+}
+
 struct EmbeddedWindowHandler<M: Send, V: EmbeddedView<M>> {
-    receiver: Arc<Receiver<M>>,
-    view: V,
+    command_receiver: Receiver<EmbeddedWindowCommand>,
+    receiver: Receiver<M>,
+    view: Option<V>,
     window_adapter: Rc<EmbeddedWindowAdapter>,
+    window_id: EmbeddedWindowId,
 }
 
-impl<E: Send, V: EmbeddedView<E>> baseview::WindowHandler for EmbeddedWindowHandler<E, V> {
-    fn on_frame(&mut self, _window: &mut baseview::Window) {
-        for message in self.receiver.try_iter() {
-            self.view.on_message(message);
-        }
+impl<E: Send, V: EmbeddedView<E> + 'static> baseview::WindowHandler
+    for EmbeddedWindowHandler<E, V>
+{
+    fn on_frame(&mut self, window: &mut baseview::Window) {
+        let Some(view) = &self.view else {
+            return;
+        };
+
+        EmbeddedPlatform::with_active_window(self.window_id, || {
+            // With every tick, even if it is not rendered, so the animation tick Slint reports to
+            // the view and its callbacks is current.
+            update_timers_and_animations();
+
+            for command in self.command_receiver.try_iter() {
+                match command {
+                    EmbeddedWindowCommand::Resize(size) => {
+                        self.window_adapter.resize_window(size);
+                    }
+                    EmbeddedWindowCommand::Invoke(f) => f(view, self.window_adapter.window()),
+                }
+            }
 
-        self.window_adapter.on_frame();
+            for message in self.receiver.try_iter() {
+                view.on_message(message);
+            }
+
+            EmbeddedPlatform::process_events();
+            self.window_adapter.on_frame(window);
+        });
     }
 
     fn on_event(
@@ -139,6 +459,99 @@ impl<E: Send, V: EmbeddedView<E>> baseview::WindowHandler for EmbeddedWindowHand
         _window: &mut baseview::Window,
         event: baseview::Event,
     ) -> baseview::EventStatus {
-        self.window_adapter.on_event(event)
+        if self.view.is_none() {
+            return baseview::EventStatus::Ignored;
+        }
+
+        EmbeddedPlatform::with_active_window(self.window_id, || self.window_adapter.on_event(event))
+    }
+}
+
+impl<M: Send, V: EmbeddedView<M>> Drop for EmbeddedWindowHandler<M, V> {
+    fn drop(&mut self) {
+        EmbeddedPlatform::unregister_window(self.window_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size_constraints(
+        min: Option<(f32, f32)>,
+        max: Option<(f32, f32)>,
+        aspect_ratio: Option<f32>,
+    ) -> EmbeddedSizeConstraints {
+        EmbeddedSizeConstraints {
+            min: min.map(|(width, height)| LogicalSize::new(width, height)),
+            max: max.map(|(width, height)| LogicalSize::new(width, height)),
+            preferred: LogicalSize::new(200.0, 200.0),
+            aspect_ratio,
+        }
+    }
+
+    #[test]
+    fn check_size_min_only() {
+        let constraints = size_constraints(Some((100.0, 50.0)), None, None);
+
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(20.0, 20.0)),
+            LogicalSize::new(100.0, 50.0)
+        );
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(1000.0, 20.0)),
+            LogicalSize::new(1000.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn check_size_max_only() {
+        let constraints = size_constraints(None, Some((400.0, 300.0)), None);
+
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(1000.0, 1000.0)),
+            LogicalSize::new(400.0, 300.0)
+        );
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(10.0, 1000.0)),
+            LogicalSize::new(10.0, 300.0)
+        );
+    }
+
+    #[test]
+    fn check_size_aspect_ratio() {
+        let constraints = size_constraints(Some((100.0, 50.0)), Some((800.0, 400.0)), Some(2.0));
+
+        // The width is kept and the height follows it.
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(300.0, 300.0)),
+            LogicalSize::new(300.0, 150.0)
+        );
+        // A clamped height changes the width back.
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(1000.0, 100.0)),
+            LogicalSize::new(800.0, 400.0)
+        );
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(10.0, 100.0)),
+            LogicalSize::new(100.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn check_size_conflicting_clamps() {
+        // The minimum wins over the maximum.
+        let constraints = size_constraints(Some((300.0, 300.0)), Some((200.0, 200.0)), None);
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(250.0, 250.0)),
+            LogicalSize::new(300.0, 300.0)
+        );
+
+        // The limits win over the aspect ratio if both can't be met.
+        let constraints = size_constraints(Some((100.0, 10.0)), Some((400.0, 50.0)), Some(1.0));
+        assert_eq!(
+            constraints.check_size(LogicalSize::new(300.0, 300.0)),
+            LogicalSize::new(100.0, 50.0)
+        );
     }
 }
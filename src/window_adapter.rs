@@ -1,40 +1,97 @@
-use i_slint_common::for_each_special_keys;
 use i_slint_core::{
-    api::{LogicalPosition, LogicalSize, PhysicalSize, Window},
-    items::PointerEventButton,
+    InternalToken,
+    api::{
+        LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, PlatformError, Window,
+        WindowPosition, WindowSize,
+    },
+    items::{KeyEvent, KeyEventType, MouseCursor, PointerEventButton},
+    lengths::LogicalRect,
     platform::{WindowEvent, update_timers_and_animations},
     renderer::Renderer,
-    window::WindowAdapter,
+    window::{
+        InputMethodRequest, WindowAdapter, WindowAdapterInternal, WindowInner, WindowProperties,
+    },
+};
+use raw_window_handle::HasDisplayHandle;
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+    sync::{Arc, Mutex},
+    time::Duration,
 };
-use std::{cell::RefCell, rc::Rc};
-
-use crate::{EmbeddedRendererType, renderer::EmbeddedRendererAdapter};
-
-#[cfg(feature = "femtovg")]
-use crate::renderer::EmbeddedFemtoVGRendererAdapter;
-
-#[cfg(feature = "skia")]
-use crate::renderer::EmbeddedSkiaRendererAdapter;
 
-#[cfg(feature = "software")]
-use crate::renderer::EmbeddedSoftwareRendererAdapter;
+use crate::{
+    EmbeddedFrameCounters, EmbeddedFramePacing, EmbeddedImeEvent, EmbeddedImeRequest,
+    EmbeddedInputMethod, EmbeddedRendererType, EmbeddedSizeConstraints,
+    error::EmbeddedRendererError,
+    executor::EmbeddedExecutor,
+    keyboard, logging,
+    platform::{EmbeddedPlatform, EmbeddedWindowId},
+    renderer::{
+        BaseviewWindowWrapper, EmbeddedRendererAdapter, EmbeddedRendererAdapters,
+        create_renderer_adapters,
+    },
+};
 
 // ---------- EmbeddedWindowAdapter ---------- //
 
 pub(crate) struct EmbeddedWindowAdapter {
     inner: RefCell<EmbeddedWindowAdapterInner>,
     slint_window: Window,
-    renderer_adapter: Box<dyn EmbeddedRendererAdapter>,
+    renderer_adapters: EmbeddedRendererAdapters,
+    renderer_index: Cell<usize>,
+    system_scale_policy: baseview::WindowScalePolicy,
+    window_wrapper: RefCell<Option<Arc<BaseviewWindowWrapper>>>,
+    this: Weak<EmbeddedWindowAdapter>,
+    parent: Weak<EmbeddedWindowAdapter>,
+    popup_window: RefCell<Option<(EmbeddedWindowId, baseview::WindowHandle)>>,
+    input_method: RefCell<Option<Box<dyn EmbeddedInputMethod>>>,
+    resize_callback: RefCell<Option<Box<dyn Fn(LogicalSize)>>>,
+    // Shared with `EmbeddedWindow` on the host thread.
+    size_constraints: Arc<Mutex<EmbeddedSizeConstraints>>,
+    executor: EmbeddedExecutor,
 }
 
 impl EmbeddedWindowAdapter {
     const LINE_PX: f32 = 60.0;
+    // baseview opens child windows on their own thread on X11, where the popup could neither reach
+    // its adapter nor be placed. Slint draws popups inside the editor there.
+    const POPUP_WINDOWS: bool = cfg!(any(target_os = "windows", target_os = "macos"));
 
     pub(crate) fn new(
         size: LogicalSize,
         user_scale_factor: f32,
         system_scale_policy: baseview::WindowScalePolicy,
-        renderer_type: EmbeddedRendererType,
+        renderer_adapters: EmbeddedRendererAdapters,
+    ) -> Rc<Self> {
+        Self::new_with_parent(
+            size,
+            user_scale_factor,
+            system_scale_policy,
+            renderer_adapters,
+            Weak::new(),
+        )
+    }
+
+    // A 100x100 window without scaling for unit tests.
+    #[cfg(test)]
+    pub(crate) fn new_for_test(renderer_adapters: EmbeddedRendererAdapters) -> Rc<Self> {
+        Self::new(
+            LogicalSize::new(100.0, 100.0),
+            1.0,
+            baseview::WindowScalePolicy::ScaleFactor(1.0),
+            renderer_adapters,
+        )
+    }
+
+    fn new_with_parent(
+        size: LogicalSize,
+        user_scale_factor: f32,
+        system_scale_policy: baseview::WindowScalePolicy,
+        renderer_adapters: EmbeddedRendererAdapters,
+        parent: Weak<EmbeddedWindowAdapter>,
     ) -> Rc<Self> {
         Rc::new_cyclic(|this| {
             let slint_window = Window::new(this.clone() as _);
@@ -42,19 +99,6 @@ impl EmbeddedWindowAdapter {
                 scale_factor: user_scale_factor,
             });
 
-            let renderer_adapter: Box<dyn EmbeddedRendererAdapter> = match renderer_type {
-                #[cfg(feature = "femtovg")]
-                EmbeddedRendererType::FemtoVG => {
-                    Box::new(EmbeddedFemtoVGRendererAdapter::default())
-                }
-                #[cfg(feature = "skia")]
-                EmbeddedRendererType::Skia => Box::new(EmbeddedSkiaRendererAdapter::default()),
-                #[cfg(feature = "software")]
-                EmbeddedRendererType::Software => {
-                    Box::new(EmbeddedSoftwareRendererAdapter::default())
-                }
-            };
-
             Self {
                 inner: RefCell::new(EmbeddedWindowAdapterInner {
                     size,
@@ -63,12 +107,32 @@ impl EmbeddedWindowAdapter {
                         baseview::WindowScalePolicy::ScaleFactor(s) => s as _,
                     },
                     user_scale_factor,
+                    position: LogicalPosition::new(0.0, 0.0),
+                    pending_mouse_cursor: None,
+                    modifiers: keyboard_types::Modifiers::empty(),
                     mouse_pos: LogicalPosition::new(0.0, 0.0),
                     mouse_down: false,
                     pending_mouse_exit: false,
+                    pending_window_size: None,
+                    close_requested: false,
+                    needs_redraw: true,
+                    frame_counters: Default::default(),
+                    frame_pacing: Default::default(),
+                    next_frame: Duration::ZERO,
+                    layout_constraints: Default::default(),
                 }),
                 slint_window,
-                renderer_adapter,
+                renderer_adapters,
+                renderer_index: Cell::new(0),
+                system_scale_policy,
+                window_wrapper: Default::default(),
+                this: this.clone(),
+                parent,
+                popup_window: Default::default(),
+                input_method: Default::default(),
+                resize_callback: Default::default(),
+                size_constraints: Default::default(),
+                executor: Default::default(),
             }
         })
     }
@@ -76,46 +140,246 @@ impl EmbeddedWindowAdapter {
     // ---------- Getter ---------- //
 
     pub(crate) fn renderer(&self) -> &dyn Renderer {
-        self.renderer_adapter.renderer()
+        self.renderer_adapter().renderer()
+    }
+
+    pub(crate) fn frame_counters(&self) -> EmbeddedFrameCounters {
+        self.inner.borrow().frame_counters
+    }
+
+    pub(crate) fn shared_size_constraints(&self) -> Arc<Mutex<EmbeddedSizeConstraints>> {
+        self.size_constraints.clone()
+    }
+
+    pub(crate) fn renderer_type(&self) -> EmbeddedRendererType {
+        self.renderer_adapters[self.renderer_index.get()].0
+    }
+
+    fn renderer_adapter(&self) -> &dyn EmbeddedRendererAdapter {
+        self.renderer_adapters[self.renderer_index.get()].1.as_ref()
     }
 
     // ---------- Setter ---------- //
 
-    pub(crate) fn set_window(&self, baseview_window: &baseview::Window) {
-        if let Err(err) = self
-            .renderer_adapter
-            .set_window(baseview_window, &self.slint_window)
-        {
-            println!("{err}");
+    pub(crate) fn set_window(
+        &self,
+        baseview_window: &baseview::Window,
+    ) -> Result<(), EmbeddedRendererError> {
+        let window_wrapper = Arc::new(BaseviewWindowWrapper::new(baseview_window));
+        if let Ok(display_handle) = window_wrapper.display_handle() {
+            EmbeddedPlatform::init_clipboard(display_handle.as_raw());
         }
+        self.set_window_wrapper(window_wrapper)
     }
 
+    // Tries the renderer adapters in order and keeps the first one which accepts the window.
+    fn set_window_wrapper(
+        &self,
+        window_wrapper: Arc<BaseviewWindowWrapper>,
+    ) -> Result<(), EmbeddedRendererError> {
+        self.window_wrapper
+            .borrow_mut()
+            .replace(window_wrapper.clone());
+
+        // Switching is fine as long as no component has been created for the window yet.
+        let mut result = Ok(());
+        for (index, (_, renderer_adapter)) in self.renderer_adapters.iter().enumerate() {
+            if let Err(err) = &result {
+                logging::warn(logging::RENDERER, format_args!("{err}, falling back"));
+            }
+            result = renderer_adapter.set_window(&window_wrapper, &self.slint_window);
+            if result.is_ok() {
+                self.renderer_index.set(index);
+                break;
+            }
+        }
+        result
+    }
+
+    pub(crate) fn set_input_method(&self, input_method: Option<Box<dyn EmbeddedInputMethod>>) {
+        *self.input_method.borrow_mut() = input_method;
+    }
+
+    pub(crate) fn spawn_local(&self, future: impl Future<Output = ()> + 'static) {
+        self.executor.spawn(future);
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_aspect_ratio(&self, aspect_ratio: Option<f32>) {
+        self.size_constraints.lock().unwrap().aspect_ratio = aspect_ratio;
+    }
+
+    pub(crate) fn set_frame_pacing(&self, frame_pacing: EmbeddedFramePacing) {
+        let mut inner = self.inner.borrow_mut();
+        inner.frame_pacing = frame_pacing;
+        inner.next_frame = Duration::ZERO;
+    }
+
+    pub(crate) fn set_resize_callback(&self, resize_callback: Option<Box<dyn Fn(LogicalSize)>>) {
+        *self.resize_callback.borrow_mut() = resize_callback;
+    }
+
+    // The window is resized to keep the logical size, so nothing gets clipped.
     pub(crate) fn set_user_scale_factor(&self, user_scale_factor: f32) {
-        let physical_size = {
+        let (physical_size, window_size) = {
             let mut inner = self.inner.borrow_mut();
             inner.user_scale_factor = user_scale_factor;
-            inner.physical_size()
+            inner.pending_window_size = Some(inner.window_size());
+            (inner.physical_size(), inner.window_size())
         };
+        self.update_size_constraints();
 
-        // TODO: Trigger resize.
-        if let Err(err) = self.renderer_adapter.renderer().resize(physical_size) {
-            println!("{err}");
+        if let Err(err) = self.renderer().resize(physical_size) {
+            logging::error(logging::RENDERER, err);
         }
 
         self.slint_window
             .dispatch_event(WindowEvent::ScaleFactorChanged {
                 scale_factor: user_scale_factor,
             });
+
+        if let Some(resize_callback) = self.resize_callback.borrow().as_ref() {
+            resize_callback(window_size);
+        }
+    }
+
+    // Sizes from the host are window sizes, like the ones passed to the resize callback.
+    pub(crate) fn resize_window(&self, window_size: WindowSize) {
+        let size = match window_size {
+            WindowSize::Logical(size) => {
+                let user_scale_factor = self.inner.borrow().user_scale_factor;
+                WindowSize::Logical(LogicalSize::new(
+                    size.width / user_scale_factor,
+                    size.height / user_scale_factor,
+                ))
+            }
+            WindowSize::Physical(size) => WindowSize::Physical(size),
+        };
+        self.resize(size);
+    }
+
+    // Publishes the layout constraints of the view in window sizes.
+    fn update_size_constraints(&self) {
+        let inner = self.inner.borrow();
+        let scale = |size: LogicalSize| {
+            LogicalSize::new(
+                size.width * inner.user_scale_factor,
+                size.height * inner.user_scale_factor,
+            )
+        };
+        let mut size_constraints = self.size_constraints.lock().unwrap();
+        size_constraints.min = inner.layout_constraints.min.map(scale);
+        size_constraints.max = inner.layout_constraints.max.map(scale);
+        size_constraints.preferred = scale(inner.layout_constraints.preferred);
+    }
+
+    // Resizes the view and the baseview window. Returns the new window size if it changed.
+    fn resize(&self, size: WindowSize) -> Option<LogicalSize> {
+        let (logical, physical, window_size) = {
+            let mut inner = self.inner.borrow_mut();
+            let size = size.to_logical(inner.scale());
+            if size == inner.size {
+                return None;
+            }
+            inner.size = size;
+            inner.pending_window_size = Some(inner.window_size());
+            (inner.size, inner.physical_size(), inner.window_size())
+        };
+        if let Err(err) = self.renderer().resize(physical) {
+            logging::error(logging::RENDERER, err);
+        }
+        self.slint_window
+            .dispatch_event(WindowEvent::Resized { size: logical });
+        Some(window_size)
     }
 
     // ---------- Events ---------- //
 
-    pub(crate) fn on_frame(&self) {
-        update_timers_and_animations();
+    // Closed with the next frame, since only the handler has access to the `baseview::Window`.
+    pub(crate) fn request_close(&self) {
+        self.inner.borrow_mut().close_requested = true;
+    }
+
+    pub(crate) fn on_frame(&self, baseview_window: &mut baseview::Window) {
+        self.count_frame();
 
-        if let Err(err) = self.renderer_adapter.render(&self.slint_window) {
-            println!("{err}");
+        let (close_requested, mouse_cursor, window_size) = {
+            let mut inner = self.inner.borrow_mut();
+            (
+                inner.close_requested,
+                inner.pending_mouse_cursor.take(),
+                inner.pending_window_size.take(),
+            )
+        };
+        if close_requested {
+            baseview_window.close();
+            return;
+        }
+        if let Some(mouse_cursor) = mouse_cursor {
+            baseview_window.set_mouse_cursor(mouse_cursor);
+        }
+        if let Some(window_size) = window_size {
+            baseview_window.resize(baseview::Size::new(
+                window_size.width as _,
+                window_size.height as _,
+            ));
+        }
+
+        // Polled with every tick, so woken tasks are not held back by the frame pacing. Their
+        // redraw is. Timers and animations are updated by the handler before, the pacing only
+        // skips the render.
+        self.poll_executor();
+        if !self.frame_due() {
+            return;
+        }
+        if let Err(err) = self.render_frame() {
+            logging::error(logging::RENDERER, err);
+        }
+    }
+
+    // Woken tasks are likely to have changed the UI.
+    pub(crate) fn poll_executor(&self) {
+        if self.executor.poll() {
+            self.request_redraw();
+        }
+    }
+
+    fn frame_due(&self) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        let max_fps = match inner.frame_pacing {
+            EmbeddedFramePacing::Host => return true,
+            EmbeddedFramePacing::MaxFps(max_fps) => max_fps.max(1),
+        };
+
+        let now = EmbeddedPlatform::now();
+        if now < inner.next_frame {
+            return false;
         }
+        // Scheduled from the last due frame to keep the rate, unless the host fell behind.
+        let interval = Duration::from_secs(1) / max_fps;
+        inner.next_frame = match inner.next_frame + interval {
+            next_frame if next_frame > now => next_frame,
+            _ => now + interval,
+        };
+        true
+    }
+
+    // The part of `on_frame` which does not need a `baseview::Window`. Only renders if Slint
+    // requested a redraw or an animation is running.
+    // Every tick counts, including the ones skipped by the frame pacing or closing the window.
+    pub(crate) fn count_frame(&self) {
+        self.inner.borrow_mut().frame_counters.frames += 1;
+    }
+
+    pub(crate) fn render_frame(&self) -> Result<(), EmbeddedRendererError> {
+        // Timers and animations were updated at the start of the tick already.
+        let needs_redraw = std::mem::take(&mut self.inner.borrow_mut().needs_redraw);
+        if !needs_redraw && !self.slint_window.has_active_animations() {
+            return Ok(());
+        }
+
+        self.inner.borrow_mut().frame_counters.rendered_frames += 1;
+        self.renderer_adapter().render(&self.slint_window)
     }
 
     pub(crate) fn on_event(&self, event: baseview::Event) -> baseview::EventStatus {
@@ -201,23 +465,9 @@ impl EmbeddedWindowAdapter {
             baseview::Event::Keyboard(key_event) => {
                 self.send_modifiers(key_event.modifiers);
 
-                let text = key_event.key.to_string();
-                macro_rules! modifier_to_char {
-                    ($($char:literal # $name:ident # $($qt:ident)|* # $($winit:ident $(($_pos:ident))?)|* # $($xkb:ident)|* ;)*) => {
-                        if false { unimplemented!() }
-
-                        $($(
-                            else if text == stringify!($winit) {
-                                $char.into()
-                            }
-                        )*)*
-
-                        else {
-                            text
-                        }
-                    };
-                }
-                let text = for_each_special_keys!(modifier_to_char).into();
+                let Some(text) = keyboard::convert_key(&key_event.key, key_event.code) else {
+                    return baseview::EventStatus::Ignored;
+                };
 
                 match key_event.state {
                     keyboard_types::KeyState::Down => {
@@ -239,13 +489,17 @@ impl EmbeddedWindowAdapter {
                 baseview::WindowEvent::Resized(info) => {
                     let (logical, physical) = {
                         let mut inner = self.inner.borrow_mut();
+                        // Baseview's logical size includes the user scale factor.
                         let logical = info.logical_size();
-                        inner.size = LogicalSize::new(logical.width as _, logical.height as _);
+                        inner.size = LogicalSize::new(
+                            logical.width as f32 / inner.user_scale_factor,
+                            logical.height as f32 / inner.user_scale_factor,
+                        );
                         inner.system_scale_factor = info.scale() as _;
                         (inner.size, inner.physical_size())
                     };
-                    if let Err(err) = self.renderer_adapter.renderer().resize(physical) {
-                        println!("{err}");
+                    if let Err(err) = self.renderer().resize(physical) {
+                        logging::error(logging::RENDERER, err);
                     }
                     self.slint_window
                         .dispatch_event(WindowEvent::Resized { size: logical });
@@ -255,6 +509,8 @@ impl EmbeddedWindowAdapter {
                         .dispatch_event(WindowEvent::WindowActiveChanged(true));
                 }
                 baseview::WindowEvent::Unfocused => {
+                    // Releases happening while unfocused are never reported.
+                    self.send_modifiers(keyboard_types::Modifiers::empty());
                     self.slint_window
                         .dispatch_event(WindowEvent::WindowActiveChanged(false));
                 }
@@ -267,6 +523,130 @@ impl EmbeddedWindowAdapter {
         baseview::EventStatus::Captured
     }
 
+    pub(crate) fn dispatch_ime_event(&self, event: EmbeddedImeEvent) {
+        let key_event = match event {
+            EmbeddedImeEvent::Preedit { text, selection } => KeyEvent {
+                event_type: KeyEventType::UpdateComposition,
+                preedit_text: text.into(),
+                preedit_selection: selection.map(|s| s.start as i32..s.end as i32),
+                ..Default::default()
+            },
+            EmbeddedImeEvent::Commit(text) => KeyEvent {
+                event_type: KeyEventType::CommitComposition,
+                text: text.into(),
+                ..Default::default()
+            },
+        };
+        WindowInner::from_pub(&self.slint_window).process_key_input(key_event);
+    }
+
+    // ---------- Popup ---------- //
+
+    fn open_popup_window(&self) -> Result<(), PlatformError> {
+        let (Some(this), Some(parent)) = (self.this.upgrade(), self.parent.upgrade()) else {
+            return Err(PlatformError::Other("Popup without parent window".into()));
+        };
+        let Some(parent_wrapper) = parent.window_wrapper.borrow().clone() else {
+            return Err(PlatformError::Other("Popup parent has no window".into()));
+        };
+
+        let window_size = self.inner.borrow().window_size();
+
+        // Popups are claimed by Slint directly, so the registry only has to hand the adapter
+        // over to the build closure. It runs before `open_parented` returns on the platforms with
+        // `POPUP_WINDOWS`.
+        let window_id = EmbeddedPlatform::register_popup_window(this);
+        let window_handle = baseview::Window::open_parented(
+            parent_wrapper,
+            baseview::WindowOpenOptions {
+                title: String::new(),
+                size: baseview::Size::new(window_size.width as _, window_size.height as _),
+                scale: self.system_scale_policy,
+            },
+            move |baseview_window| {
+                let window_adapter = EmbeddedPlatform::window_adapter(window_id);
+                if let Some(window_adapter) = &window_adapter {
+                    if let Err(err) = window_adapter.set_window(baseview_window) {
+                        logging::error(logging::RENDERER, err);
+                    }
+                } else {
+                    logging::error(
+                        logging::WINDOW,
+                        format_args!("Popup {window_id:?} opened on a foreign thread"),
+                    );
+                }
+                EmbeddedPopupHandler {
+                    window_adapter,
+                    window_id,
+                }
+            },
+        );
+
+        self.popup_window
+            .borrow_mut()
+            .replace((window_id, window_handle));
+        self.place_popup_window();
+        Ok(())
+    }
+
+    // Moves the child window to `inner.position`, which is relative to the editor window.
+    fn place_popup_window(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(target_os = "windows")] {
+                use windows_sys::Win32::UI::WindowsAndMessaging::{
+                    SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, SetWindowPos,
+                };
+
+                let Some(RawWindowHandle::Win32(handle)) = self.raw_window_handle() else {
+                    return;
+                };
+                let position = {
+                    let inner = self.inner.borrow();
+                    inner.position.to_physical(inner.scale())
+                };
+                unsafe {
+                    SetWindowPos(
+                        handle.hwnd.get() as _,
+                        std::ptr::null_mut(),
+                        position.x,
+                        position.y,
+                        0,
+                        0,
+                        SWP_NOACTIVATE | SWP_NOSIZE | SWP_NOZORDER,
+                    );
+                }
+            } else if #[cfg(target_os = "macos")] {
+                let Some(RawWindowHandle::AppKit(handle)) = self.raw_window_handle() else {
+                    return;
+                };
+                // Points are scaled by the system already. baseview's views are flipped, so the
+                // origin is the top left corner like on Windows.
+                let origin = {
+                    let inner = self.inner.borrow();
+                    NSPoint {
+                        x: (inner.position.x * inner.user_scale_factor) as _,
+                        y: (inner.position.y * inner.user_scale_factor) as _,
+                    }
+                };
+                let view: &objc2::runtime::AnyObject = unsafe { handle.ns_view.cast().as_ref() };
+                let () = unsafe { objc2::msg_send![view, setFrameOrigin: origin] };
+            }
+        }
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    fn raw_window_handle(&self) -> Option<RawWindowHandle> {
+        let window_wrapper = self.window_wrapper.borrow();
+        Some(window_wrapper.as_ref()?.window_handle().ok()?.as_raw())
+    }
+
+    fn close_popup_window(&self) {
+        if let Some((window_id, mut window_handle)) = self.popup_window.borrow_mut().take() {
+            window_handle.close();
+            EmbeddedPlatform::unregister_window(window_id);
+        }
+    }
+
     // ---------- Util ---------- //
 
     fn convert_button(button: baseview::MouseButton) -> PointerEventButton {
@@ -280,6 +660,40 @@ impl EmbeddedWindowAdapter {
         }
     }
 
+    fn convert_mouse_cursor(cursor: MouseCursor) -> baseview::MouseCursor {
+        match cursor {
+            MouseCursor::Default => baseview::MouseCursor::Default,
+            MouseCursor::None => baseview::MouseCursor::Hidden,
+            MouseCursor::Help => baseview::MouseCursor::Help,
+            MouseCursor::Pointer => baseview::MouseCursor::Hand,
+            MouseCursor::Progress => baseview::MouseCursor::PtrWorking,
+            MouseCursor::Wait => baseview::MouseCursor::Working,
+            MouseCursor::Crosshair => baseview::MouseCursor::Crosshair,
+            MouseCursor::Text => baseview::MouseCursor::Text,
+            MouseCursor::Alias => baseview::MouseCursor::Alias,
+            MouseCursor::Copy => baseview::MouseCursor::Copy,
+            MouseCursor::Move => baseview::MouseCursor::Move,
+            MouseCursor::NoDrop => baseview::MouseCursor::PtrNotAllowed,
+            MouseCursor::NotAllowed => baseview::MouseCursor::NotAllowed,
+            MouseCursor::Grab => baseview::MouseCursor::Hand,
+            MouseCursor::Grabbing => baseview::MouseCursor::HandGrabbing,
+            MouseCursor::ColResize => baseview::MouseCursor::ColResize,
+            MouseCursor::RowResize => baseview::MouseCursor::RowResize,
+            MouseCursor::NResize => baseview::MouseCursor::NResize,
+            MouseCursor::EResize => baseview::MouseCursor::EResize,
+            MouseCursor::SResize => baseview::MouseCursor::SResize,
+            MouseCursor::WResize => baseview::MouseCursor::WResize,
+            MouseCursor::NeResize => baseview::MouseCursor::NeResize,
+            MouseCursor::NwResize => baseview::MouseCursor::NwResize,
+            MouseCursor::SeResize => baseview::MouseCursor::SeResize,
+            MouseCursor::SwResize => baseview::MouseCursor::SwResize,
+            MouseCursor::EwResize => baseview::MouseCursor::EwResize,
+            MouseCursor::NsResize => baseview::MouseCursor::NsResize,
+            MouseCursor::NeswResize => baseview::MouseCursor::NeswResize,
+            MouseCursor::NwseResize => baseview::MouseCursor::NwseResize,
+        }
+    }
+
     // Swap control and meta according to slint's docs.
     fn convert_modifier(modifier: keyboard_types::Modifiers) -> &'static str {
         if modifier == keyboard_types::Modifiers::ALT {
@@ -303,7 +717,8 @@ impl EmbeddedWindowAdapter {
         }
     }
 
-    fn send_modifiers(&self, modifiers: keyboard_types::Modifiers) {
+    fn modifier_texts(modifiers: keyboard_types::Modifiers) -> Vec<&'static str> {
+        let mut texts = Vec::new();
         for modifier in [
             keyboard_types::Modifiers::ALT,
             keyboard_types::Modifiers::ALT_GRAPH,
@@ -324,10 +739,24 @@ impl EmbeddedWindowAdapter {
                 continue;
             }
 
+            // Control and super share a key, so it is only pressed once.
             let text = Self::convert_modifier(modifier);
-            if text.is_empty() {
+            if text.is_empty() || texts.contains(&text) {
                 continue;
             }
+            texts.push(text);
+        }
+        texts
+    }
+
+    fn send_modifiers(&self, modifiers: keyboard_types::Modifiers) {
+        let (released, pressed) = self.inner.borrow_mut().update_modifiers(modifiers);
+
+        for text in released {
+            self.slint_window
+                .dispatch_event(WindowEvent::KeyReleased { text: text.into() });
+        }
+        for text in pressed {
             self.slint_window
                 .dispatch_event(WindowEvent::KeyPressed { text: text.into() });
         }
@@ -339,13 +768,157 @@ impl WindowAdapter for EmbeddedWindowAdapter {
         &self.slint_window
     }
 
+    fn set_visible(&self, visible: bool) -> Result<(), PlatformError> {
+        if self.parent.strong_count() == 0 {
+            return Ok(());
+        }
+
+        if visible {
+            if self.popup_window.borrow().is_none() {
+                self.open_popup_window()?;
+            }
+        } else {
+            self.close_popup_window();
+        }
+        Ok(())
+    }
+
+    fn position(&self) -> Option<PhysicalPosition> {
+        if self.parent.strong_count() == 0 {
+            return None;
+        }
+
+        let inner = self.inner.borrow();
+        Some(inner.position.to_physical(inner.scale()))
+    }
+
+    fn set_position(&self, position: WindowPosition) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            inner.position = match position {
+                WindowPosition::Logical(position) => position,
+                WindowPosition::Physical(position) => position.to_logical(inner.scale()),
+            };
+        }
+        if self.popup_window.borrow().is_some() {
+            self.place_popup_window();
+        }
+    }
+
+    // Requested by the view, so the host is told about the new size.
+    fn set_size(&self, size: WindowSize) {
+        let Some(window_size) = self.resize(size) else {
+            return;
+        };
+        if let Some(resize_callback) = self.resize_callback.borrow().as_ref() {
+            resize_callback(window_size);
+        }
+    }
+
     fn size(&self) -> PhysicalSize {
         self.inner.borrow().physical_size()
     }
 
+    fn update_window_properties(&self, properties: WindowProperties<'_>) {
+        let constraints = properties.layout_constraints();
+        {
+            let mut inner = self.inner.borrow_mut();
+            inner.layout_constraints.min = constraints.min;
+            inner.layout_constraints.max = constraints.max;
+            inner.layout_constraints.preferred = constraints.preferred;
+        }
+        self.update_size_constraints();
+    }
+
+    // Picked up by the next `on_frame`.
+    fn request_redraw(&self) {
+        self.inner.borrow_mut().needs_redraw = true;
+    }
+
     fn renderer(&self) -> &dyn Renderer {
         self.renderer()
     }
+
+    fn internal(&self, _: InternalToken) -> Option<&dyn WindowAdapterInternal> {
+        Some(self)
+    }
+}
+
+impl WindowAdapterInternal for EmbeddedWindowAdapter {
+    fn create_popup(&self, geometry: LogicalRect) -> Option<Rc<dyn WindowAdapter>> {
+        if !Self::POPUP_WINDOWS {
+            return None;
+        }
+        let inner = self.inner.borrow();
+
+        // Popups of popups are parented to the editor window, like Slint does for its own.
+        let (parent, position) = match self.parent.upgrade() {
+            Some(_) => (
+                self.parent.clone(),
+                LogicalPosition::new(
+                    inner.position.x + geometry.origin.x,
+                    inner.position.y + geometry.origin.y,
+                ),
+            ),
+            None => (
+                self.this.clone(),
+                LogicalPosition::new(geometry.origin.x, geometry.origin.y),
+            ),
+        };
+
+        let window_adapter = Self::new_with_parent(
+            LogicalSize::new(geometry.width(), geometry.height()),
+            inner.user_scale_factor,
+            self.system_scale_policy,
+            create_renderer_adapters(self.renderer_type()),
+            parent,
+        );
+        {
+            let mut popup_inner = window_adapter.inner.borrow_mut();
+            popup_inner.system_scale_factor = inner.system_scale_factor;
+            popup_inner.position = position;
+        }
+
+        // Without a window, Slint falls back to drawing the popup inside the editor.
+        if let Err(err) = window_adapter.open_popup_window() {
+            logging::warn(logging::WINDOW, err);
+            return None;
+        }
+        Some(window_adapter as _)
+    }
+
+    fn input_method_request(&self, request: InputMethodRequest) {
+        // Slint reports the cursor rectangle in view coordinates.
+        let user_scale_factor = self.inner.borrow().user_scale_factor;
+        let position = |p: LogicalPosition| {
+            LogicalPosition::new(p.x * user_scale_factor, p.y * user_scale_factor)
+        };
+        let size = |s: LogicalSize| {
+            LogicalSize::new(s.width * user_scale_factor, s.height * user_scale_factor)
+        };
+
+        let request = match request {
+            InputMethodRequest::Enable(properties) => EmbeddedImeRequest::Enable {
+                cursor_position: position(properties.cursor_rect_origin),
+                cursor_size: size(properties.cursor_rect_size),
+            },
+            InputMethodRequest::Update(properties) => EmbeddedImeRequest::Update {
+                cursor_position: position(properties.cursor_rect_origin),
+                cursor_size: size(properties.cursor_rect_size),
+            },
+            InputMethodRequest::Disable => EmbeddedImeRequest::Disable,
+            _ => return,
+        };
+
+        if let Some(input_method) = self.input_method.borrow().as_ref() {
+            input_method.on_request(request);
+        }
+    }
+
+    // Applied in `on_frame`, since only the handler has access to the `baseview::Window`.
+    fn set_mouse_cursor(&self, cursor: MouseCursor) {
+        self.inner.borrow_mut().pending_mouse_cursor = Some(Self::convert_mouse_cursor(cursor));
+    }
 }
 
 // ---------- EmbeddedWindowAdapterInner ---------- //
@@ -354,12 +927,31 @@ struct EmbeddedWindowAdapterInner {
     size: LogicalSize,
     system_scale_factor: f32,
     user_scale_factor: f32,
+    position: LogicalPosition,
+    pending_mouse_cursor: Option<baseview::MouseCursor>,
+    pending_window_size: Option<LogicalSize>,
+    close_requested: bool,
+    modifiers: keyboard_types::Modifiers,
     mouse_pos: LogicalPosition,
     mouse_down: bool,
     pending_mouse_exit: bool,
+    needs_redraw: bool,
+    frame_counters: EmbeddedFrameCounters,
+    frame_pacing: EmbeddedFramePacing,
+    next_frame: Duration,
+    // In view sizes, the aspect ratio is kept in the shared constraints only.
+    layout_constraints: EmbeddedSizeConstraints,
 }
 
 impl EmbeddedWindowAdapterInner {
+    // The size of the baseview window, which is scaled by the user scale factor only.
+    fn window_size(&self) -> LogicalSize {
+        LogicalSize::new(
+            self.size.width * self.user_scale_factor,
+            self.size.height * self.user_scale_factor,
+        )
+    }
+
     fn physical_size(&self) -> PhysicalSize {
         self.size.to_physical(self.scale())
     }
@@ -367,4 +959,244 @@ impl EmbeddedWindowAdapterInner {
     fn scale(&self) -> f32 {
         self.system_scale_factor * self.user_scale_factor
     }
+
+    // Returns the modifier keys to release and to press to get from the last seen modifiers to
+    // `modifiers`.
+    fn update_modifiers(
+        &mut self,
+        modifiers: keyboard_types::Modifiers,
+    ) -> (Vec<&'static str>, Vec<&'static str>) {
+        let old = EmbeddedWindowAdapter::modifier_texts(self.modifiers);
+        let new = EmbeddedWindowAdapter::modifier_texts(modifiers);
+        self.modifiers = modifiers;
+
+        let released = old.iter().filter(|t| !new.contains(*t)).copied().collect();
+        let pressed = new.iter().filter(|t| !old.contains(*t)).copied().collect();
+        (released, pressed)
+    }
+}
+
+// ---------- EmbeddedPopupHandler ---------- //
+
+#[cfg(target_os = "macos")]
+#[repr(C)]
+struct NSPoint {
+    x: f64,
+    y: f64,
+}
+
+#[cfg(target_os = "macos")]
+unsafe impl objc2::Encode for NSPoint {
+    const ENCODING: objc2::Encoding = objc2::Encoding::Struct(
+        "CGPoint",
+        &[
+            <f64 as objc2::Encode>::ENCODING,
+            <f64 as objc2::Encode>::ENCODING,
+        ],
+    );
+}
+
+struct EmbeddedPopupHandler {
+    window_adapter: Option<Rc<EmbeddedWindowAdapter>>,
+    window_id: EmbeddedWindowId,
+}
+
+impl baseview::WindowHandler for EmbeddedPopupHandler {
+    fn on_frame(&mut self, window: &mut baseview::Window) {
+        if let Some(window_adapter) = &self.window_adapter {
+            update_timers_and_animations();
+            window_adapter.on_frame(window);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        _window: &mut baseview::Window,
+        event: baseview::Event,
+    ) -> baseview::EventStatus {
+        match &self.window_adapter {
+            Some(window_adapter) => window_adapter.on_event(event),
+            None => baseview::EventStatus::Ignored,
+        }
+    }
+}
+
+impl Drop for EmbeddedPopupHandler {
+    fn drop(&mut self) {
+        EmbeddedPlatform::unregister_window(self.window_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use keyboard_types::Modifiers;
+
+    use super::*;
+
+    #[cfg(feature = "software")]
+    use crate::error::EmbeddedRendererErrorKind;
+    #[cfg(feature = "software")]
+    use i_slint_renderer_software::SoftwareRenderer;
+    #[cfg(feature = "software")]
+    use raw_window_handle::{RawDisplayHandle, RawWindowHandle, WebDisplayHandle, WebWindowHandle};
+
+    #[cfg(feature = "software")]
+    fn window_wrapper() -> Arc<BaseviewWindowWrapper> {
+        Arc::new(BaseviewWindowWrapper::from_raw(
+            RawDisplayHandle::Web(WebDisplayHandle::new()),
+            RawWindowHandle::Web(WebWindowHandle::new(1)),
+        ))
+    }
+
+    // Never renders, the software renderer only backs `renderer()`.
+    #[cfg(feature = "software")]
+    struct StubRendererAdapter {
+        name: &'static str,
+        accepts_window: bool,
+        renderer: SoftwareRenderer,
+    }
+
+    #[cfg(feature = "software")]
+    impl EmbeddedRendererAdapter for StubRendererAdapter {
+        fn set_window(
+            &self,
+            _window_wrapper: &Arc<BaseviewWindowWrapper>,
+            _slint_window: &Window,
+        ) -> Result<(), EmbeddedRendererError> {
+            match self.accepts_window {
+                true => Ok(()),
+                false => Err(EmbeddedRendererError::new(
+                    self.name,
+                    EmbeddedRendererErrorKind::SetWindow,
+                    "rejected",
+                )),
+            }
+        }
+
+        fn render(&self, _slint_window: &Window) -> Result<(), EmbeddedRendererError> {
+            Ok(())
+        }
+
+        fn renderer(&self) -> &dyn Renderer {
+            &self.renderer
+        }
+    }
+
+    #[cfg(feature = "software")]
+    fn stub(
+        renderer_type: EmbeddedRendererType,
+        name: &'static str,
+        accepts_window: bool,
+    ) -> (EmbeddedRendererType, Box<dyn EmbeddedRendererAdapter>) {
+        (
+            renderer_type,
+            Box::new(StubRendererAdapter {
+                name,
+                accepts_window,
+                renderer: SoftwareRenderer::default(),
+            }) as Box<dyn EmbeddedRendererAdapter>,
+        )
+    }
+
+    #[cfg(feature = "software")]
+    #[test]
+    fn renderer_fallback() {
+        let window_adapter = EmbeddedWindowAdapter::new_for_test(vec![
+            stub(EmbeddedRendererType::Auto, "First", false),
+            stub(EmbeddedRendererType::Software, "Second", true),
+            stub(EmbeddedRendererType::Auto, "Third", true),
+        ]);
+
+        assert!(window_adapter.set_window_wrapper(window_wrapper()).is_ok());
+        assert_eq!(window_adapter.renderer_index.get(), 1);
+        assert_eq!(
+            window_adapter.renderer_type(),
+            EmbeddedRendererType::Software
+        );
+    }
+
+    #[cfg(feature = "software")]
+    #[test]
+    fn renderer_fallback_exhausted() {
+        let window_adapter = EmbeddedWindowAdapter::new_for_test(vec![
+            stub(EmbeddedRendererType::Auto, "First", false),
+            stub(EmbeddedRendererType::Auto, "Second", false),
+        ]);
+
+        // The error of the last adapter is the one reported.
+        let err = window_adapter
+            .set_window_wrapper(window_wrapper())
+            .unwrap_err();
+        assert_eq!(err.renderer(), "Second");
+        assert_eq!(err.kind(), EmbeddedRendererErrorKind::SetWindow);
+        assert_eq!(window_adapter.renderer_index.get(), 0);
+    }
+
+    #[test]
+    fn modifier_transitions() {
+        let window_adapter = EmbeddedWindowAdapter::new_for_test(Vec::new());
+        let mut inner = window_adapter.inner.borrow_mut();
+
+        assert_eq!(
+            inner.update_modifiers(Modifiers::SHIFT),
+            (vec![], vec!["\u{0010}"])
+        );
+        // Held modifiers are not pressed again.
+        assert_eq!(inner.update_modifiers(Modifiers::SHIFT), (vec![], vec![]));
+        assert_eq!(
+            inner.update_modifiers(Modifiers::SHIFT | Modifiers::ALT),
+            (vec![], vec!["\u{0012}"])
+        );
+        assert_eq!(
+            inner.update_modifiers(Modifiers::ALT),
+            (vec!["\u{0010}"], vec![])
+        );
+        assert_eq!(
+            inner.update_modifiers(Modifiers::CONTROL),
+            (vec!["\u{0012}"], vec!["\u{0017}"])
+        );
+        assert_eq!(
+            inner.update_modifiers(Modifiers::empty()),
+            (vec!["\u{0017}"], vec![])
+        );
+    }
+
+    #[test]
+    fn control_and_super_share_a_key() {
+        let window_adapter = EmbeddedWindowAdapter::new_for_test(Vec::new());
+        let mut inner = window_adapter.inner.borrow_mut();
+
+        assert_eq!(
+            inner.update_modifiers(Modifiers::CONTROL | Modifiers::SUPER),
+            (vec![], vec!["\u{0017}"])
+        );
+        // Still held through super.
+        assert_eq!(inner.update_modifiers(Modifiers::SUPER), (vec![], vec![]));
+        assert_eq!(inner.update_modifiers(Modifiers::CONTROL), (vec![], vec![]));
+        assert_eq!(
+            inner.update_modifiers(Modifiers::empty()),
+            (vec!["\u{0017}"], vec![])
+        );
+    }
+
+    #[test]
+    fn unfocused_releases_modifiers() {
+        let window_adapter = EmbeddedWindowAdapter::new_for_test(Vec::new());
+        window_adapter
+            .inner
+            .borrow_mut()
+            .update_modifiers(Modifiers::SHIFT | Modifiers::CONTROL);
+
+        window_adapter.on_event(baseview::Event::Window(baseview::WindowEvent::Unfocused));
+        assert_eq!(window_adapter.inner.borrow().modifiers, Modifiers::empty());
+
+        // Pressed again when focus comes back with the keys still held.
+        assert_eq!(
+            window_adapter
+                .inner
+                .borrow_mut()
+                .update_modifiers(Modifiers::SHIFT),
+            (vec![], vec!["\u{0010}"])
+        );
+    }
 }